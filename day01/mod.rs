@@ -0,0 +1,156 @@
+use crate::{ParseError, Solution};
+use regex_lite::Regex;
+
+fn num_to_int(num: &str) -> u32 {
+    if let Ok(i) = num.parse::<u32>() {
+        return i;
+    }
+
+    match num {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        _ => panic!("Can not convert string: {num}"),
+    }
+}
+
+/// First and last digit of `line` matching `numbers`, if it has any.
+fn nums(line: &str, numbers: &str) -> Option<(u32, u32)> {
+    let double = format!(r"({numbers}).*({numbers})");
+    let single = format!(r"({numbers})");
+    let combined = format!(r"(?:{double})|(?:{single})");
+    let re = Regex::new(&combined).unwrap();
+
+    let captures = re.captures(line)?;
+    match (captures.get(1), captures.get(2), captures.get(3)) {
+        (Some(a), Some(b), None) => {
+            let num1 = num_to_int(a.as_str());
+            let num2 = num_to_int(b.as_str());
+            Some((num1, num2))
+        }
+        (None, None, Some(a)) => {
+            let num1 = num_to_int(a.as_str());
+            Some((num1, num1))
+        }
+        _ => panic!("No numbers found in captures: {captures:?}"),
+    }
+}
+
+/// Sums the calibration values of all lines. Lines without any of `numbers`, like spelled out
+/// digits in part 1, have no value and are skipped.
+fn calibrate(lines: &[String], numbers: &str) -> u32 {
+    let mut result: u32 = 0;
+
+    for (a, b) in lines.iter().filter_map(|line| nums(line, numbers)) {
+        result += a * 10 + b;
+    }
+
+    result
+}
+
+fn part1(lines: &[String]) -> u32 {
+    calibrate(lines, r"[1-9]")
+}
+
+const DIGITS: &str = r"one|two|three|four|five|six|seven|eight|nine|[1-9]";
+
+fn part2(lines: &[String]) -> u32 {
+    calibrate(lines, DIGITS)
+}
+
+/// Reads the calibration document, in which every line has to hold a digit, written out or not.
+fn parse(s: &str) -> Result<Vec<String>, ParseError> {
+    let re = Regex::new(DIGITS).unwrap();
+
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if re.is_match(line) {
+                Ok(line.to_string())
+            } else {
+                Err(ParseError::new(0, line, "a digit")
+                    .with_hint("every line hides a digit, like '7' or 'seven'")
+                    .on_line(i + 1))
+            }
+        })
+        .collect()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+    use crate::Part;
+
+    #[test]
+    fn test_num_to_int() {
+        assert_eq!(7, num_to_int("7"));
+        assert_eq!(7, num_to_int("seven"));
+        assert_eq!(1, num_to_int("one"));
+    }
+
+    #[test]
+    fn test_nums() {
+        assert_eq!(Some((7, 7)), nums("treb7uchet", r"[1-9]"));
+        assert_eq!(Some((2, 4)), nums("xtwone3four", DIGITS));
+        assert_eq!(Some((8, 3)), nums("eightwothree", DIGITS));
+        assert_eq!(None, nums("eightwothree", r"[1-9]"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("two1nine\nabc\n7pqrstsixteen\n").unwrap_err();
+        assert_eq!((2, 1, "abc"), (e.line, e.column, e.text.as_str()));
+
+        // Part 1 skips lines whose digits are all spelled out.
+        let lines = parse(&load("day01/test2.txt").unwrap()).unwrap();
+        assert_eq!(11 + 22 + 33 + 42 + 24 + 77, part1(&lines));
+    }
+
+    #[test]
+    fn test_part1() {
+        for input in ["day01/test1.txt", "day01/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part1(&parse(&content).unwrap());
+            assert_eq!(expected(1, Part::One, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for input in ["day01/test2.txt", "day01/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part2(&parse(&content).unwrap());
+            assert_eq!(expected(1, Part::Two, input), result.to_string());
+        }
+    }
+}
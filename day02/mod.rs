@@ -0,0 +1,172 @@
+use crate::error::{expect, number, split};
+use crate::{ParseError, Solution};
+use std::cmp::max;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Game {
+    id: u32,
+    rounds: Vec<Round>,
+}
+
+#[derive(Debug)]
+pub struct Round {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = expect(s, "Game ", 0)?;
+        let (id, rounds) = rest
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(s.len(), "", "': '"))?;
+
+        let id: u32 = number(id, 5)?;
+
+        let offset = s.len() - rounds.len();
+        let rounds: Vec<Round> = split(rounds, "; ")
+            .map(|(i, r)| r.parse().map_err(|e: ParseError| e.shift(offset + i)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Game { id, rounds })
+    }
+}
+
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut round = Round {
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+
+        for (i, cubes) in split(s, ", ") {
+            let (count, color) = cubes
+                .split_once(' ')
+                .ok_or_else(|| ParseError::new(i + cubes.len(), "", "a color"))?;
+
+            let count: u32 = number(count, i)?;
+
+            match color {
+                "red" => round.red = count,
+                "green" => round.green = count,
+                "blue" => round.blue = count,
+                x => {
+                    let offset = i + cubes.len() - x.len();
+                    return Err(ParseError::new(offset, x, "'red', 'green' or 'blue'"));
+                }
+            }
+        }
+
+        Ok(round)
+    }
+}
+
+fn maxs(game: &Game) -> (u32, u32, u32) {
+    let mut red = 0;
+    let mut green = 0;
+    let mut blue = 0;
+
+    for round in &game.rounds {
+        red = max(round.red, red);
+        green = max(round.green, green);
+        blue = max(round.blue, blue);
+    }
+
+    (red, green, blue)
+}
+
+fn part1(games: &[Game]) -> u32 {
+    let mut result = 0;
+
+    for game in games {
+        let (r, g, b) = maxs(game);
+
+        if r <= 12 && g <= 13 && b <= 14 {
+            result += game.id;
+        }
+    }
+
+    result
+}
+
+fn part2(games: &[Game]) -> u32 {
+    let mut result = 0;
+
+    for game in games {
+        let (r, g, b) = maxs(game);
+        let power = r * g * b;
+        result += power
+    }
+
+    result
+}
+
+fn parse(s: &str) -> Result<Vec<Game>, ParseError> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e: ParseError| e.on_line(i + 1)))
+        .collect()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+    use crate::Part;
+
+    #[test]
+    fn test_maxs() {
+        let game: Game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse()
+            .unwrap();
+        assert_eq!((4, 2, 6), maxs(&game));
+    }
+
+    #[test]
+    fn test_part1() {
+        for input in ["day02/test1.txt", "day02/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part1(&parse(&content).unwrap());
+            assert_eq!(expected(2, Part::One, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for input in ["day02/test1.txt", "day02/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part2(&parse(&content).unwrap());
+            assert_eq!(expected(2, Part::Two, input), result.to_string());
+        }
+    }
+}
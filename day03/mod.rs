@@ -0,0 +1,179 @@
+use crate::error::number;
+use crate::{ParseError, Solution};
+use itertools::Itertools;
+use regex_lite::Regex;
+
+#[derive(Debug)]
+pub struct Number {
+    num: u32,
+    row: i32,
+    col_start: i32,
+    col_end: i32,
+}
+
+#[derive(Debug)]
+pub struct Symbol {
+    character: char,
+    row: i32,
+    col: i32,
+}
+
+fn find_numbers(map: &str) -> Result<Vec<Number>, ParseError> {
+    let re = Regex::new(r"\d+").unwrap();
+
+    let mut result = vec![];
+    for (i, line) in map.lines().enumerate() {
+        for m in re.find_iter(line) {
+            result.push(Number {
+                num: number(m.as_str(), m.start()).map_err(|e| e.on_line(i + 1))?,
+                row: i as i32,
+                col_start: m.start() as i32,
+                col_end: m.end() as i32 - 1,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+fn find_symbols(map: &str) -> Vec<Symbol> {
+    let re = Regex::new(r"[^\.\d]").unwrap();
+
+    let mut result = vec![];
+    for (i, line) in map.lines().enumerate() {
+        for m in re.find_iter(line) {
+            result.push(Symbol {
+                character: m.as_str().parse().unwrap(),
+                row: i as i32,
+                col: m.start() as i32,
+            });
+        }
+    }
+
+    result
+}
+
+fn neighbors(number: &Number, symbol: &Symbol) -> bool {
+    if i32::abs(number.row - symbol.row) > 1 {
+        return false;
+    }
+
+    if i32::abs(number.col_start - symbol.col) <= 1 {
+        return true;
+    }
+
+    if i32::abs(number.col_end - symbol.col) <= 1 {
+        return true;
+    }
+
+    false
+}
+
+fn part1(numbers: &[Number], symbols: &[Symbol]) -> u32 {
+    let mut result = 0;
+
+    for n in numbers {
+        for s in symbols {
+            if neighbors(n, s) {
+                result += n.num;
+            }
+        }
+    }
+
+    result
+}
+
+fn part2(numbers: &[Number], symbols: &[Symbol]) -> u32 {
+    let mut result = 0;
+
+    for s in symbols {
+        if s.character != '*' {
+            continue;
+        };
+
+        if let Some((a, b)) = numbers
+            .iter()
+            .filter(|n| neighbors(n, s))
+            .map(|n| n.num)
+            .collect_tuple()
+        {
+            result += a * b;
+        }
+    }
+
+    result
+}
+
+fn parse(s: &str) -> Result<(Vec<Number>, Vec<Symbol>), ParseError> {
+    Ok((find_numbers(s)?, find_symbols(s)))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = (Vec<Number>, Vec<Symbol>);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0, &input.1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.0, &input.1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+    use crate::Part;
+
+    #[test]
+    fn test_neighbors() {
+        let number = Number {
+            num: 467,
+            row: 0,
+            col_start: 0,
+            col_end: 2,
+        };
+        let symbol = |row, col| Symbol {
+            character: '*',
+            row,
+            col,
+        };
+
+        assert!(neighbors(&number, &symbol(1, 3)));
+        assert!(neighbors(&number, &symbol(0, 3)));
+        assert!(!neighbors(&number, &symbol(1, 4)));
+        assert!(!neighbors(&number, &symbol(2, 1)));
+    }
+
+    #[test]
+    fn test_part1() {
+        for input in ["day03/test1.txt", "day03/input.txt"] {
+            let content = load(input).unwrap();
+            let (numbers, symbols) = parse(&content).unwrap();
+            let result = part1(&numbers, &symbols);
+            assert_eq!(expected(3, Part::One, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for input in ["day03/test1.txt", "day03/input.txt"] {
+            let content = load(input).unwrap();
+            let (numbers, symbols) = parse(&content).unwrap();
+            let result = part2(&numbers, &symbols);
+            assert_eq!(expected(3, Part::Two, input), result.to_string());
+        }
+    }
+}
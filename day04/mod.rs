@@ -0,0 +1,172 @@
+use crate::error::{expect, number, words};
+use crate::{ParseError, Solution};
+use std::str::FromStr;
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct Card {
+    id: u32,
+    winning: Vec<u32>,
+    have: Vec<u32>,
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = expect(s, "Card", 0)?;
+        let (id, numbers) = rest
+            .split_once(':')
+            .ok_or_else(|| ParseError::new(s.len(), "", "':'"))?;
+
+        let id: u32 = number(
+            id.trim_start(),
+            s.len() - rest.len() + id.len() - id.trim_start().len(),
+        )?;
+
+        let (winning, have) = numbers
+            .split_once(" |")
+            .ok_or_else(|| ParseError::new(s.len(), "", "'|'"))?;
+
+        let offset = s.len() - numbers.len();
+        let winning: Vec<u32> = words(winning)
+            .map(|(i, w)| number(w, offset + i))
+            .collect::<Result<_, _>>()?;
+
+        let offset = s.len() - have.len();
+        let have: Vec<u32> = words(have)
+            .map(|(i, w)| number(w, offset + i))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Card { id, winning, have })
+    }
+}
+
+fn intersection(card: &Card) -> Vec<u32> {
+    let mut result: Vec<u32> = vec![];
+    for i in &card.have {
+        if card.winning.contains(i) {
+            result.push(*i);
+        }
+    }
+
+    result
+}
+
+fn part1(cards: &[Card]) -> u32 {
+    let mut result: u32 = 0;
+    for c in cards {
+        let common = intersection(c).len() as u32;
+        if common > 0 {
+            result += u32::pow(2, common - 1);
+        }
+    }
+
+    result
+}
+
+fn part2(cards: &[Card]) -> u32 {
+    let mut card_count: Vec<u32> = vec![1; cards.len()];
+
+    for c in cards {
+        let common = intersection(c).len();
+
+        // Copies never go past the end of the table.
+        let at = (c.id - 1) as usize;
+        for i in (at + 1)..(at + common + 1).min(cards.len()) {
+            card_count[i] += card_count[at];
+        }
+    }
+
+    card_count.iter().sum()
+}
+
+/// Reads the cards, which have to be numbered from 1 in order: part 2 finds cards by number.
+fn parse(s: &str) -> Result<Vec<Card>, ParseError> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let card: Card = line.parse().map_err(|e: ParseError| e.on_line(i + 1))?;
+
+            if card.id as usize != i + 1 {
+                let (offset, word) = words(line).nth(1).unwrap_or((0, line));
+                return Err(ParseError::new(
+                    offset,
+                    word.trim_end_matches(':'),
+                    &format!("card {}", i + 1),
+                )
+                .with_hint("cards are numbered from 1, one per line")
+                .on_line(i + 1));
+            }
+
+            Ok(card)
+        })
+        .collect()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+    use crate::Part;
+
+    #[test]
+    fn test_intersection() {
+        let card: Card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+            .parse()
+            .unwrap();
+        assert_eq!(vec![83, 86, 17, 48], intersection(&card));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("Card 1: 1 2 | 1 2\nCard 3: 1 2 | 1 2\n").unwrap_err();
+        assert_eq!((2, 6, "3"), (e.line, e.column, e.text.as_str()));
+
+        let e = parse("Card  0: 1 | 1\n").unwrap_err();
+        assert_eq!((1, 7, "0"), (e.line, e.column, e.text.as_str()));
+
+        // Copies past the end of the table are dropped.
+        assert_eq!(1, part2(&parse("Card 1: 1 2 | 1 2\n").unwrap()));
+    }
+
+    #[test]
+    fn test_part1() {
+        for input in ["day04/test1.txt", "day04/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part1(&parse(&content).unwrap());
+            assert_eq!(expected(4, Part::One, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for input in ["day04/test1.txt", "day04/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part2(&parse(&content).unwrap());
+            assert_eq!(expected(4, Part::Two, input), result.to_string());
+        }
+    }
+}
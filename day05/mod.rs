@@ -0,0 +1,634 @@
+// use regex_lite::Regex;
+use crate::error::{expect, number, words};
+use crate::{ParseError, Solution};
+use std::collections::HashMap;
+
+mod piecewise;
+pub use piecewise::{Piece, Piecewise};
+
+/// One `<from>-to-<to> map:` block of the almanac.
+#[derive(Debug)]
+pub struct Recipe {
+    pub from: String,
+    pub to: String,
+    maps: Vec<(u64, u64, u64)>,
+    /// 1-based line of each of `maps`.
+    lines: Vec<usize>,
+}
+
+impl Recipe {
+    fn map(&self, i: u64) -> u64 {
+        for (d, s, l) in &self.maps {
+            if i >= *s && i - *s < *l {
+                return d.saturating_add(i - *s);
+            }
+        }
+
+        i
+    }
+
+    /// Maps every number of the half-open range `[start, end)`, splitting it wherever it crosses
+    /// the border of a mapping. Returns the resulting ranges in no particular order.
+    fn map_range(&self, (start, end): (u64, u64)) -> Vec<(u64, u64)> {
+        let mut todo = vec![(start, end)];
+        let mut result = vec![];
+
+        while let Some((a, b)) = todo.pop() {
+            if a >= b {
+                continue;
+            }
+
+            // Like `map`, the first mapping wins. Any earlier mapping does not touch `[a, b)`.
+            match self
+                .maps
+                .iter()
+                .find(|(_, s, l)| a < s.saturating_add(*l) && *s < b)
+            {
+                Some((d, s, l)) => {
+                    let lo = a.max(*s);
+                    let hi = b.min(s.saturating_add(*l));
+
+                    result.push((d.saturating_add(lo - *s), d.saturating_add(hi - *s)));
+                    todo.push((a, lo));
+                    todo.push((hi, b));
+                }
+                None => result.push((a, b)),
+            }
+        }
+
+        result
+    }
+
+    /// Every number `map` sends into the half-open range `[start, end)`, as sorted ranges.
+    pub fn unmap_range(&self, range: (u64, u64)) -> Vec<(u64, u64)> {
+        Piecewise::of(self).preimage(range)
+    }
+
+    /// Every number `map` sends to `i`, as sorted ranges.
+    pub fn unmap(&self, i: u64) -> Vec<(u64, u64)> {
+        self.unmap_range((i, i.saturating_add(1)))
+    }
+
+    /// Mappings that are shadowed by an earlier one, have no effect or do not fit into a `u64`.
+    /// `map` tolerates all of them, but they hint at a broken input.
+    pub fn validate(&self) -> Vec<ParseError> {
+        let mut result = vec![];
+
+        for (i, (d, s, l)) in self.maps.iter().enumerate() {
+            let line = self.lines[i];
+            let text = format!("{d} {s} {l}");
+            let e = |expected: &str, hint: &str| {
+                ParseError::new(0, &text, expected)
+                    .on_line(line)
+                    .with_hint(hint)
+            };
+
+            if *l == 0 {
+                result.push(e("a length of at least 1", "the mapping has no effect"));
+            }
+
+            if s.checked_add(*l).is_none() || d.checked_add(*l).is_none() {
+                let start = if s > d { s } else { d };
+                result.push(e(
+                    "a range that fits into a u64",
+                    &format!("{start} + {l} is larger than {}", u64::MAX),
+                ));
+            }
+
+            for (j, (_, s2, l2)) in self.maps[..i].iter().enumerate() {
+                let lo = *s.max(s2);
+                let hi = s.saturating_add(*l).min(s2.saturating_add(*l2));
+                if lo < hi {
+                    result.push(e(
+                        &format!("sources apart from line {}", self.lines[j]),
+                        &format!(
+                            "{lo} to {} are already mapped on line {}",
+                            hi - 1,
+                            self.lines[j]
+                        ),
+                    ));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// The recipes leading from category `from` to category `to`, in order. Empty if both are the
+/// same, `None` if `to` can not be reached from `from`.
+pub fn route<'a>(from: &str, to: &str, rs: &'a [Recipe]) -> Option<Vec<&'a Recipe>> {
+    let mut result = vec![];
+    let mut at = from;
+
+    while at != to {
+        let r = rs.iter().find(|r| r.from == at)?;
+        if result.len() == rs.len() {
+            return None;
+        }
+
+        result.push(r);
+        at = &r.to;
+    }
+
+    Some(result)
+}
+
+/// Converts `i` of category `from` into category `to`, if there is a way.
+pub fn convert(i: u64, from: &str, to: &str, rs: &[Recipe]) -> Option<u64> {
+    Some(route(from, to, rs)?.iter().fold(i, |i, r| r.map(i)))
+}
+
+/// Seeds, seed ranges and the chain of recipes from seed to location.
+pub type Almanac = (Vec<u64>, Vec<(u64, u64)>, Vec<Recipe>);
+
+fn apply_recipes(n: u64, maps: &[Recipe]) -> u64 {
+    let mut result: u64 = n;
+    for m in maps {
+        result = m.map(result);
+    }
+    result
+}
+
+/// Seeds that end up in the locations `[start, end)`, as sorted ranges.
+pub fn unapply_recipes(range: (u64, u64), maps: &[Recipe]) -> Vec<(u64, u64)> {
+    Piecewise::compose(maps).preimage(range)
+}
+
+fn parse_map(line: &str) -> Result<(u64, u64, u64), ParseError> {
+    let numbers: Vec<(usize, &str)> = words(line).collect();
+
+    if let Some((o, w)) = numbers.get(3) {
+        return Err(ParseError::new(*o, w, "end of line"));
+    }
+
+    let [(d, ds), (s, ss), (l, ls)] = numbers[..] else {
+        return Err(ParseError::new(
+            line.len(),
+            "",
+            "'<destination> <source> <length>'",
+        ));
+    };
+
+    Ok((number(ds, d)?, number(ss, s)?, number(ls, l)?))
+}
+
+fn parse_header(header: &str) -> Result<(String, String), ParseError> {
+    let Some(categories) = header.strip_suffix(" map:") else {
+        return Err(ParseError::new(header.len() - 1, ":", "' map:'"));
+    };
+
+    match categories.split_once("-to-") {
+        Some((from, to)) if !from.is_empty() && !to.is_empty() => {
+            Ok((from.to_string(), to.to_string()))
+        }
+        _ => Err(ParseError::new(
+            0,
+            categories,
+            "'<source>-to-<destination>'",
+        )),
+    }
+}
+
+/// Puts the recipes, each with the line of its header, into order from seed to location. Every
+/// recipe has to be part of that chain.
+fn chain(mut recipes: Vec<(usize, Recipe)>) -> Result<Vec<Recipe>, ParseError> {
+    let mut lines: HashMap<&str, usize> = HashMap::new();
+    for (line, r) in &recipes {
+        if let Some(first) = lines.insert(&r.from, *line) {
+            return Err(ParseError::new(0, &r.from, "a category without a map")
+                .on_line(*line)
+                .with_hint(&format!("'{}' is already mapped on line {first}", r.from)));
+        }
+    }
+
+    let mut result: Vec<Recipe> = vec![];
+    let mut at = "seed".to_string();
+    let mut last = 1;
+
+    while let Some(i) = recipes.iter().position(|(_, r)| r.from == at) {
+        let (line, r) = recipes.swap_remove(i);
+        at = r.to.clone();
+        last = line;
+        result.push(r);
+    }
+
+    if let Some((line, r)) = recipes.iter().min_by_key(|(line, _)| *line) {
+        return Err(ParseError::new(0, &r.from, &format!("'{at}'"))
+            .on_line(*line)
+            .with_hint(&format!(
+                "the map is not connected to the chain from seed, which ends at '{at}'"
+            )));
+    }
+
+    if at != "location" {
+        return Err(ParseError::new(0, &at, "'location'")
+            .on_line(last)
+            .with_hint(&format!("there is no map from '{at}' towards location")));
+    }
+
+    Ok(result)
+}
+
+fn parse(s: &str) -> Result<Almanac, ParseError> {
+    let mut lines = s.lines().enumerate();
+
+    let (_, first) = lines.next().unwrap_or((0, ""));
+    let fields: Vec<(usize, &str)> = words(expect(first, "seeds: ", 0).map_err(|e| e.on_line(1))?)
+        .map(|(i, w)| (7 + i, w))
+        .collect();
+    let seeds: Vec<u64> = fields
+        .iter()
+        .map(|(i, w)| number(w, *i).map_err(|e| e.on_line(1)))
+        .collect::<Result<_, _>>()?;
+
+    if seeds.is_empty() {
+        return Err(ParseError::new(first.len(), "", "a seed").on_line(1));
+    }
+
+    if !seeds.len().is_multiple_of(2) {
+        return Err(ParseError::new(first.len(), "", "a range length").on_line(1));
+    }
+
+    let ranges: Vec<(u64, u64)> = seeds
+        .chunks(2)
+        .zip(fields.chunks(2))
+        .map(|(pair, words)| {
+            let (start, length) = (pair[0], pair[1]);
+            let (i, w) = words[1];
+            match start.checked_add(length) {
+                _ if length == 0 => Err(ParseError::new(i, w, "a length of at least 1")
+                    .on_line(1)
+                    .with_hint("part 2 reads the seeds as pairs of start and length")),
+                Some(_) => Ok((start, length)),
+                None => Err(ParseError::new(i, w, "a range that fits into a u64")
+                    .on_line(1)
+                    .with_hint(&format!("the range from {start} does not fit into a u64"))),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut recipes: Vec<(usize, Recipe)> = vec![];
+    for (i, line) in lines {
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.ends_with(':') {
+            let (from, to) = parse_header(line).map_err(|e| e.on_line(i + 1))?;
+            recipes.push((
+                i + 1,
+                Recipe {
+                    from,
+                    to,
+                    maps: vec![],
+                    lines: vec![],
+                },
+            ));
+            continue;
+        }
+
+        let (_, recipe) = recipes.last_mut().ok_or_else(|| {
+            ParseError::new(0, line, "a map header")
+                .on_line(i + 1)
+                .with_hint("mappings have to follow a header like 'seed-to-soil map:'")
+        })?;
+
+        recipe
+            .maps
+            .push(parse_map(line).map_err(|e| e.on_line(i + 1))?);
+        recipe.lines.push(i + 1);
+    }
+
+    Ok((seeds, ranges, chain(recipes)?))
+}
+
+/// Like `parse`, but refuses almanacs with mappings that `Recipe::validate` complains about.
+pub fn parse_strict(s: &str) -> Result<Almanac, ParseError> {
+    let almanac = parse(s)?;
+
+    match Day05::validate(&almanac).into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(almanac),
+    }
+}
+
+fn part1(seeds: &[u64], rs: &[Recipe]) -> u64 {
+    seeds.iter().map(|i| apply_recipes(*i, rs)).min().unwrap()
+}
+
+/// Seed ranges are `(start, length)` pairs, which `parse` made sure are not empty and end
+/// within the u64 range.
+fn part2(ranges: &[(u64, u64)], rs: &[Recipe]) -> u64 {
+    let mut current: Vec<(u64, u64)> = ranges.iter().map(|(b, l)| (*b, b + l)).collect();
+
+    for r in rs {
+        current = current.into_iter().flat_map(|c| r.map_range(c)).collect();
+    }
+
+    current.iter().map(|(a, _)| *a).min().unwrap()
+}
+
+/// Tries every single seed. Only feasible for small ranges, but useful to check `part2`.
+pub fn part2_brute_force(ranges: &[(u64, u64)], rs: &[Recipe]) -> u64 {
+    let mut min: u64 = u64::MAX;
+
+    for (b, l) in ranges {
+        for i in *b..(b + l) {
+            min = u64::min(min, apply_recipes(i, rs));
+        }
+    }
+
+    min
+}
+
+/// Walks the locations upwards and stops at the first one that has a seed.
+pub fn part2_from_locations(ranges: &[(u64, u64)], rs: &[Recipe]) -> u64 {
+    let chain = Piecewise::compose(rs);
+
+    // No piece starts or ends inside of these, so within each of them the locations of a seed
+    // range are in order.
+    let mut borders: Vec<u64> = chain
+        .pieces()
+        .iter()
+        .flat_map(|p| [chain.apply(p.start), chain.apply(p.end - 1) + 1])
+        .collect();
+    borders.sort();
+    borders.dedup();
+
+    for w in borders.windows(2) {
+        let found = chain
+            .preimage((w[0], w[1]))
+            .into_iter()
+            .flat_map(|(a, b)| {
+                ranges
+                    .iter()
+                    .map(move |(s, l)| (a.max(*s), b.min(s + l)))
+                    .filter(|(lo, hi)| lo < hi)
+            })
+            .map(|(lo, _)| chain.apply(lo))
+            .filter(|i| (w[0]..w[1]).contains(i))
+            .min();
+
+        if let Some(location) = found {
+            return location;
+        }
+    }
+
+    unreachable!("every seed has a location")
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0, &input.2)
+    }
+
+    fn validate(input: &Self::Input) -> Vec<ParseError> {
+        input.2.iter().flat_map(|r| r.validate()).collect()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.1, &input.2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+    use crate::Part;
+
+    #[test]
+    fn test_recipe_map() {
+        let recipe = Recipe {
+            from: "seed".to_string(),
+            to: "soil".to_string(),
+            maps: vec![(50, 98, 2), (52, 50, 48)],
+            lines: vec![4, 5],
+        };
+        assert_eq!(81, recipe.map(79));
+        assert_eq!(50, recipe.map(98));
+        assert_eq!(51, recipe.map(99));
+        assert_eq!(10, recipe.map(10));
+        assert_eq!(100, recipe.map(100));
+    }
+
+    #[test]
+    fn test_part1() {
+        for input in ["day05/test1.txt", "day05/input.txt"] {
+            let content = load(input).unwrap();
+            let (seeds, _, recipes) = parse(&content).unwrap();
+            let result = part1(&seeds, &recipes);
+            assert_eq!(expected(5, Part::One, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_recipe_map_range() {
+        let recipe = Recipe {
+            from: "seed".to_string(),
+            to: "soil".to_string(),
+            maps: vec![(50, 98, 2), (52, 50, 48)],
+            lines: vec![4, 5],
+        };
+
+        let mut mapped = recipe.map_range((45, 101));
+        mapped.sort();
+        assert_eq!(vec![(45, 50), (50, 52), (52, 100), (100, 101)], mapped);
+
+        for i in 45..101 {
+            assert!(mapped
+                .iter()
+                .any(|(a, b)| (*a..*b).contains(&recipe.map(i))));
+        }
+    }
+
+    #[test]
+    fn test_recipe_unmap() {
+        let recipe = Recipe {
+            from: "seed".to_string(),
+            to: "soil".to_string(),
+            maps: vec![(50, 98, 2), (52, 50, 48)],
+            lines: vec![4, 5],
+        };
+        assert_eq!(vec![(49, 50), (98, 100)], recipe.unmap_range((49, 52)));
+        assert_eq!(vec![(79, 80)], recipe.unmap(81));
+        assert_eq!(vec![(98, 99)], recipe.unmap(50));
+
+        for (start, end) in [(0, 120), (45, 60), (50, 52), (98, 100)] {
+            let unmapped = recipe.unmap_range((start, end));
+            for i in 0..150 {
+                let inside = unmapped.iter().any(|(a, b)| (*a..*b).contains(&i));
+                assert_eq!((start..end).contains(&recipe.map(i)), inside, "{i}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_unapply_recipes() {
+        let content = load("day05/test1.txt").unwrap();
+        let (_, _, recipes) = parse(&content).unwrap();
+
+        // Seed 13 is the only one in the example that ends up in location 35.
+        let seeds = unapply_recipes((35, 36), &recipes);
+        assert_eq!(vec![(13, 14)], seeds);
+
+        let seeds = unapply_recipes((0, 50), &recipes);
+        for i in 0..200 {
+            let inside = seeds.iter().any(|(a, b)| (*a..*b).contains(&i));
+            assert_eq!(apply_recipes(i, &recipes) < 50, inside, "{i}");
+        }
+    }
+
+    /// The example with its map blocks in reverse order.
+    fn reversed(content: &str) -> String {
+        let (seeds, blocks) = content.split_once("\n\n").unwrap();
+        let mut blocks: Vec<&str> = blocks.trim_end().split("\n\n").collect();
+        blocks.reverse();
+        format!("{seeds}\n\n{}\n", blocks.join("\n\n"))
+    }
+
+    #[test]
+    fn test_categories() {
+        let content = load("day05/test1.txt").unwrap();
+        let (seeds, _, recipes) = parse(&reversed(&content)).unwrap();
+        assert_eq!(35, part1(&seeds, &recipes));
+
+        let categories: Vec<&str> = route("soil", "humidity", &recipes)
+            .unwrap()
+            .iter()
+            .map(|r| r.to.as_str())
+            .collect();
+        assert_eq!(
+            vec!["fertilizer", "water", "light", "temperature", "humidity"],
+            categories
+        );
+        assert!(route("humidity", "soil", &recipes).is_none());
+        assert!(route("seed", "seed", &recipes).unwrap().is_empty());
+
+        // Seed 79 has soil 81 and humidity 78.
+        assert_eq!(Some(78), convert(81, "soil", "humidity", &recipes));
+        assert_eq!(None, convert(81, "soil", "weather", &recipes));
+
+        let soil_to_humidity = Piecewise::compose(route("soil", "humidity", &recipes).unwrap());
+        assert_eq!(78, soil_to_humidity.apply(81));
+    }
+
+    #[test]
+    fn test_parse_chain() {
+        let content = load("day05/test1.txt").unwrap();
+
+        let e = parse(&content.replace("water-to-light", "water-to-lamp")).unwrap_err();
+        assert_eq!((22, "light".to_string()), (e.line, e.text));
+
+        let e =
+            parse(&content.replace("light-to-temperature", "water-to-temperature")).unwrap_err();
+        assert_eq!((22, "water".to_string()), (e.line, e.text));
+
+        let e = parse(&content.replace("humidity-to-location", "humidity-to-place")).unwrap_err();
+        assert_eq!((31, "place".to_string()), (e.line, e.text));
+
+        let e = parse(&content.replace("seed-to-soil map", "seed-soil map")).unwrap_err();
+        assert_eq!((3, "seed-soil".to_string()), (e.line, e.text));
+    }
+
+    #[test]
+    fn test_validate() {
+        for input in ["day05/test1.txt", "day05/input.txt"] {
+            let content = load(input).unwrap();
+            let (_, _, recipes) = parse(&content).unwrap();
+            assert!(recipes.iter().all(|r| r.validate().is_empty()), "{input}");
+            assert!(parse_strict(&content).is_ok());
+        }
+
+        let content = "\
+seeds: 79 14
+
+seed-to-location map:
+50 98 2
+52 50 48
+10 60 0
+0 18446744073709551610 10
+";
+        let almanac = parse(content).unwrap();
+        assert_eq!(2, Day05::validate(&almanac).len());
+        let (seeds, _, recipes) = almanac;
+        assert_eq!(14, part1(&seeds, &recipes));
+
+        let problems: Vec<(usize, String)> = recipes[0]
+            .validate()
+            .into_iter()
+            .map(|e| (e.line, e.expected))
+            .collect();
+        assert_eq!(
+            vec![
+                (6, "a length of at least 1".to_string()),
+                (7, "a range that fits into a u64".to_string()),
+            ],
+            problems
+        );
+
+        let e = parse(&content.replace("79 14", "79 14 18446744073709551610 10")).unwrap_err();
+        assert_eq!((1, 35, "10"), (e.line, e.column, e.text.as_str()));
+        let e = parse(&content.replace("79 14", "79 0")).unwrap_err();
+        assert_eq!(
+            (1, 11, "a length of at least 1"),
+            (e.line, e.column, e.expected.as_str())
+        );
+
+        let e = parse_strict(&content.replace("52 50 48", "52 50 49")).unwrap_err();
+        assert_eq!(
+            (5, Some("98 to 98 are already mapped on line 4".to_string())),
+            (e.line, e.hint)
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        for input in ["day05/test1.txt", "day05/input.txt"] {
+            let content = load(input).unwrap();
+            let (_, ranges, recipes) = parse(&content).unwrap();
+            let result = part2(&ranges, &recipes);
+            assert_eq!(expected(5, Part::Two, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_part2_brute_force() {
+        let content = load("day05/test1.txt").unwrap();
+        let (_, ranges, recipes) = parse(&content).unwrap();
+        assert_eq!(
+            part2(&ranges, &recipes),
+            part2_brute_force(&ranges, &recipes)
+        );
+
+        // The real recipes, but only a few thousand seeds from the start of every range.
+        let content = load("day05/input.txt").unwrap();
+        let (_, ranges, recipes) = parse(&content).unwrap();
+        let small: Vec<(u64, u64)> = ranges.iter().map(|(b, l)| (*b, (*l).min(5000))).collect();
+        assert_eq!(part2(&small, &recipes), part2_brute_force(&small, &recipes));
+    }
+
+    #[test]
+    fn test_part2_from_locations() {
+        for input in ["day05/test1.txt", "day05/input.txt"] {
+            let content = load(input).unwrap();
+            let (_, ranges, recipes) = parse(&content).unwrap();
+            let result = part2_from_locations(&ranges, &recipes);
+            assert_eq!(expected(5, Part::Two, input), result.to_string());
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use super::Recipe;
+use std::fmt::{self, Display};
+
+/// Numbers in `[start, end)` are moved by `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub start: u64,
+    pub end: u64,
+    pub offset: i128,
+}
+
+impl Piece {
+    fn apply(&self, i: u64) -> u64 {
+        (i as i128 + self.offset).clamp(0, u64::MAX as i128) as u64
+    }
+}
+
+/// A mapping of `[0, u64::MAX)` onto numbers as sorted, gapless pieces, where neighbouring pieces
+/// always have a different offset. Two mappings that agree on every number are equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piecewise {
+    pieces: Vec<Piece>,
+}
+
+impl Piecewise {
+    pub fn identity() -> Piecewise {
+        Piecewise {
+            pieces: vec![Piece {
+                start: 0,
+                end: u64::MAX,
+                offset: 0,
+            }],
+        }
+    }
+
+    /// Builds a mapping from pieces covering `[0, u64::MAX)` in order, merging neighbours with
+    /// the same offset.
+    fn merged(pieces: impl IntoIterator<Item = Piece>) -> Piecewise {
+        let mut result: Vec<Piece> = vec![];
+
+        for p in pieces {
+            if p.start >= p.end {
+                continue;
+            }
+
+            match result.last_mut() {
+                Some(last) if last.offset == p.offset && last.end == p.start => last.end = p.end,
+                _ => result.push(p),
+            }
+        }
+
+        Piecewise { pieces: result }
+    }
+
+    pub fn of(recipe: &Recipe) -> Piecewise {
+        let mut borders: Vec<u64> = vec![0, u64::MAX];
+        for (_, s, l) in &recipe.maps {
+            borders.push(*s);
+            borders.push(s.saturating_add(*l));
+        }
+        borders.sort();
+        borders.dedup();
+
+        // Between two borders every number is handled by the same mapping as the first one.
+        Piecewise::merged(borders.windows(2).map(|w| {
+            let offset = recipe
+                .maps
+                .iter()
+                .find(|(_, s, l)| w[0] >= *s && w[0] - *s < *l)
+                .map_or(0, |(d, s, _)| *d as i128 - *s as i128);
+
+            Piece {
+                start: w[0],
+                end: w[1],
+                offset,
+            }
+        }))
+    }
+
+    /// A chain of recipes, like a whole almanac or a [`super::route`], as one mapping.
+    pub fn compose<'a>(recipes: impl IntoIterator<Item = &'a Recipe>) -> Piecewise {
+        recipes
+            .into_iter()
+            .fold(Piecewise::identity(), |acc, r| acc.then(&Piecewise::of(r)))
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    fn find(&self, i: u64) -> usize {
+        self.pieces.partition_point(|p| p.end <= i)
+    }
+
+    /// Maps a single number in `O(log n)`.
+    pub fn apply(&self, i: u64) -> u64 {
+        match self.pieces.get(self.find(i)) {
+            Some(p) => p.apply(i),
+            None => i,
+        }
+    }
+
+    /// All numbers mapped into `[start, end)`, as sorted ranges.
+    pub fn preimage(&self, (start, end): (u64, u64)) -> Vec<(u64, u64)> {
+        let mut result: Vec<(u64, u64)> = vec![];
+
+        for p in &self.pieces {
+            let lo = (start as i128 - p.offset).max(p.start as i128);
+            let hi = (end as i128 - p.offset).min(p.end as i128);
+            if lo >= hi {
+                continue;
+            }
+
+            let (lo, hi) = (lo as u64, hi as u64);
+            match result.last_mut() {
+                Some(last) if last.1 == lo => last.1 = hi,
+                _ => result.push((lo, hi)),
+            }
+        }
+
+        result
+    }
+
+    /// Applies `self` first, then `next`.
+    pub fn then(&self, next: &Piecewise) -> Piecewise {
+        let mut pieces = vec![];
+
+        for p in &self.pieces {
+            let from = (p.start as i128 + p.offset).clamp(0, u64::MAX as i128) as u64;
+            let to = (p.end as i128 + p.offset).clamp(0, u64::MAX as i128) as u64;
+
+            let mut at = from;
+            for q in &next.pieces[next.find(from).min(next.pieces.len())..] {
+                if at >= to {
+                    break;
+                }
+
+                let end = q.end.min(to);
+                pieces.push(Piece {
+                    start: (at as i128 - p.offset) as u64,
+                    end: (end as i128 - p.offset) as u64,
+                    offset: p.offset + q.offset,
+                });
+                at = end;
+            }
+        }
+
+        Piecewise::merged(pieces)
+    }
+
+    /// Ranges of numbers `self` and `other` map differently.
+    pub fn differences(&self, other: &Piecewise) -> Vec<(u64, u64)> {
+        let mut borders: Vec<u64> = self
+            .pieces
+            .iter()
+            .chain(&other.pieces)
+            .flat_map(|p| [p.start, p.end])
+            .collect();
+        borders.sort();
+        borders.dedup();
+
+        let mut result: Vec<(u64, u64)> = vec![];
+        for w in borders.windows(2) {
+            if self.apply(w[0]) == other.apply(w[0]) {
+                continue;
+            }
+
+            match result.last_mut() {
+                Some(last) if last.1 == w[0] => last.1 = w[1],
+                _ => result.push((w[0], w[1])),
+            }
+        }
+
+        result
+    }
+}
+
+impl Display for Piecewise {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for p in &self.pieces {
+            writeln!(
+                f,
+                "[{}, {}) -> [{}, {}) {:+}",
+                p.start,
+                p.end,
+                p.apply(p.start),
+                p.apply(p.end - 1) as u128 + 1,
+                p.offset
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{apply_recipes, parse};
+    use super::*;
+    use crate::input::load;
+
+    #[test]
+    fn test_of() {
+        let recipe = Recipe {
+            from: "seed".to_string(),
+            to: "soil".to_string(),
+            maps: vec![(50, 98, 2), (52, 50, 48)],
+            lines: vec![4, 5],
+        };
+        let expected = "\
+[0, 50) -> [0, 50) +0
+[50, 98) -> [52, 100) +2
+[98, 100) -> [50, 52) -48
+[100, 18446744073709551615) -> [100, 18446744073709551615) +0
+";
+        assert_eq!(expected, Piecewise::of(&recipe).to_string());
+    }
+
+    #[test]
+    fn test_compose() {
+        for input in ["day05/test1.txt", "day05/input.txt"] {
+            let content = load(input).unwrap();
+            let (seeds, _, recipes) = parse(&content).unwrap();
+            let chain = Piecewise::compose(&recipes);
+
+            let borders = chain.pieces().iter().flat_map(|p| [p.start, p.end - 1]);
+            for i in seeds.into_iter().chain(borders).chain(0..100) {
+                assert_eq!(apply_recipes(i, &recipes), chain.apply(i), "{input}: {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_differences() {
+        let content = load("day05/test1.txt").unwrap();
+        let (_, _, mut recipes) = parse(&content).unwrap();
+        let chain = Piecewise::compose(&recipes);
+        assert_eq!(chain, Piecewise::compose(&recipes));
+        assert!(chain.differences(&chain).is_empty());
+
+        // Only seeds that end up at humidity 93 to 96 see the moved mapping.
+        recipes.last_mut().unwrap().maps[1].0 += 1;
+        let changed = Piecewise::compose(&recipes);
+        assert_ne!(chain, changed);
+
+        let differences = chain.differences(&changed);
+        assert!(!differences.is_empty());
+        for i in 0..200 {
+            let differs = differences.iter().any(|(a, b)| (*a..*b).contains(&i));
+            assert_eq!(chain.apply(i) != changed.apply(i), differs, "{i}");
+        }
+    }
+}
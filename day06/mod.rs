@@ -0,0 +1,533 @@
+use crate::error::{number, words};
+use crate::json::Json;
+use crate::{ParseError, Part, Report, Solution};
+use num_bigint::BigUint;
+use num_integer::Roots;
+use num_traits::CheckedMul;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// What race arithmetic needs. Implemented by `u64`, `u128` and `BigUint`.
+pub trait Number:
+    Clone
+    + Ord
+    + Debug
+    + Display
+    + From<u8>
+    + Roots
+    + CheckedMul
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+}
+
+impl<T> Number for T where
+    T: Clone
+        + Ord
+        + Debug
+        + Display
+        + From<u8>
+        + Roots
+        + CheckedMul
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+{
+}
+
+/// A race whose `time²` and `4 * distance` fit into `T`.
+#[derive(Debug)]
+pub struct Race<T = u64> {
+    time: T,
+    distance: T,
+}
+
+impl<T: Number> Race<T> {
+    fn beats(&self, hold: &T) -> bool {
+        (self.time.clone() - hold.clone()) * hold.clone() > self.distance
+    }
+
+    /// The shortest and the longest winning hold, if there is any.
+    ///
+    /// Holding `h` wins if `h * (time - h) > distance`, so the winning holds lie strictly between
+    /// the roots `(time ± sqrt(time² - 4 * distance)) / 2`. The integer square root can be off by
+    /// one from the real root, which the loops correct.
+    pub fn holds(&self) -> Option<(T, T)> {
+        let (t, d) = (self.time.clone(), self.distance.clone());
+        let (zero, one) = (T::from(0), T::from(1));
+
+        let square = t.clone() * t.clone();
+        let four_d = T::from(4) * d;
+        if square < four_d {
+            return None;
+        }
+
+        let half = t.clone() / T::from(2);
+        let mut lo = (t.clone() - (square - four_d).sqrt()) / T::from(2);
+        while lo > zero && self.beats(&(lo.clone() - one.clone())) {
+            lo = lo - one.clone();
+        }
+        while lo <= half && !self.beats(&lo) {
+            lo = lo + one.clone();
+        }
+
+        (lo <= half).then(|| (lo.clone(), t - lo))
+    }
+
+    /// Number of winning holds in `O(1)`.
+    pub fn wins(&self) -> T {
+        self.holds()
+            .map_or(T::from(0), |(lo, hi)| hi - lo + T::from(1))
+    }
+
+    /// The hold that goes furthest, holding for half of the race, and how far it goes.
+    pub fn best(&self) -> (T, T) {
+        let hold = self.time.clone() / T::from(2);
+        let distance = (self.time.clone() - hold.clone()) * hold.clone();
+        (hold, distance)
+    }
+
+    /// Time, record, shortest and longest winning hold, wins, best hold, its distance and by how
+    /// much it beats the record.
+    fn row(&self) -> Vec<Json> {
+        let (hold, distance) = self.best();
+        let holds = self.holds();
+        let margin = (distance > self.distance).then(|| distance.clone() - self.distance.clone());
+
+        vec![
+            Json::number(&self.time),
+            Json::number(&self.distance),
+            holds.as_ref().map(|(lo, _)| Json::number(lo)).into(),
+            holds.as_ref().map(|(_, hi)| Json::number(hi)).into(),
+            Json::number(self.wins()),
+            Json::number(hold),
+            Json::number(distance),
+            margin.map(Json::number).into(),
+        ]
+    }
+}
+
+impl Race<u64> {
+    /// Counts the winning holds one by one, outwards from the middle of the race.
+    pub fn wins_scan(&self) -> u64 {
+        let center = self.time / 2;
+        let mut wins = 0;
+
+        for i in (0..center).rev() {
+            if (self.time - i) * i <= self.distance {
+                break;
+            }
+
+            wins += 1;
+        }
+
+        for i in center..self.time {
+            if (self.time - i) * i <= self.distance {
+                break;
+            }
+
+            wins += 1;
+        }
+
+        wins
+    }
+}
+
+/// A race in the narrowest type its arithmetic fits into.
+#[derive(Debug)]
+pub enum AnyRace {
+    U64(Race<u64>),
+    U128(Race<u128>),
+    Big(Race<BigUint>),
+}
+
+fn narrow<T>(time: &BigUint, distance: &BigUint) -> Option<Race<T>>
+where
+    T: Number + for<'a> TryFrom<&'a BigUint>,
+{
+    let race = Race {
+        time: T::try_from(time).ok()?,
+        distance: T::try_from(distance).ok()?,
+    };
+
+    race.time.checked_mul(&race.time)?;
+    race.distance.checked_mul(&T::from(4))?;
+    Some(race)
+}
+
+impl AnyRace {
+    pub fn new(time: BigUint, distance: BigUint) -> AnyRace {
+        if let Some(race) = narrow(&time, &distance) {
+            AnyRace::U64(race)
+        } else if let Some(race) = narrow(&time, &distance) {
+            AnyRace::U128(race)
+        } else {
+            AnyRace::Big(Race { time, distance })
+        }
+    }
+
+    pub fn wins(&self) -> BigUint {
+        match self {
+            AnyRace::U64(r) => r.wins().into(),
+            AnyRace::U128(r) => r.wins().into(),
+            AnyRace::Big(r) => r.wins(),
+        }
+    }
+
+    fn row(&self) -> Vec<Json> {
+        match self {
+            AnyRace::U64(r) => r.row(),
+            AnyRace::U128(r) => r.row(),
+            AnyRace::Big(r) => r.row(),
+        }
+    }
+}
+
+/// The values of one `<label>: <values>` line, with where they start.
+struct Row<'a> {
+    line: usize,
+    offset: usize,
+    values: &'a str,
+}
+
+/// Finds the `Time:` and `Distance:` rows by their label, in any order. Other rows, like a
+/// `Record holder:`, are skipped.
+fn labels(input: &str) -> Result<(Row<'_>, Row<'_>), ParseError> {
+    let hint = "races are given by a 'Time:' and a 'Distance:' line";
+    let (mut time, mut distance): (Option<Row>, Option<Row>) = (None, None);
+
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let Some((label, values)) = line.split_once(':') else {
+            let start = line.len() - line.trim_start().len();
+            let found = line.trim().split([' ', '\t']).next().unwrap_or_default();
+            return Err(ParseError::new(start, found, "a label like 'Time:'")
+                .on_line(i + 1)
+                .with_hint(hint));
+        };
+
+        let slot = match label.trim() {
+            "Time" => &mut time,
+            "Distance" => &mut distance,
+            _ => continue,
+        };
+
+        if let Some(first) = slot {
+            let start = line.len() - line.trim_start().len();
+            return Err(
+                ParseError::new(start, label.trim(), "a single line per label")
+                    .on_line(i + 1)
+                    .with_hint(&format!(
+                        "'{}:' is already given on line {}",
+                        label.trim(),
+                        first.line
+                    )),
+            );
+        }
+
+        *slot = Some(Row {
+            line: i + 1,
+            offset: label.len() + 1,
+            values,
+        });
+    }
+
+    let end = input.lines().count() + 1;
+    let missing = |label: &str| {
+        ParseError::new(0, "", &format!("a '{label}:' line"))
+            .on_line(end)
+            .with_hint(hint)
+    };
+
+    Ok((
+        time.ok_or_else(|| missing("Time"))?,
+        distance.ok_or_else(|| missing("Distance"))?,
+    ))
+}
+
+fn numbers(s: &str, offset: usize, line: usize) -> Result<Vec<BigUint>, ParseError> {
+    words(s)
+        .map(|(i, w)| number(w, offset + i).map_err(|e| e.on_line(line)))
+        .collect()
+}
+
+fn digits(s: &str, offset: usize, line: usize) -> Result<BigUint, ParseError> {
+    s.chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .map_err(|_| {
+            let column = offset + s.len() - s.trim_start().len();
+            ParseError::new(column, s.trim(), "a number").on_line(line)
+        })
+}
+
+fn parse1(input: &str) -> Result<Vec<AnyRace>, ParseError> {
+    let (ts, ds) = labels(input)?;
+
+    let times = numbers(ts.values, ts.offset, ts.line)?;
+    let distances = numbers(ds.values, ds.offset, ds.line)?;
+
+    if times.len() != distances.len() {
+        let (longer, shorter) = if times.len() > distances.len() {
+            (&ts, &ds)
+        } else {
+            (&ds, &ts)
+        };
+        let n = times.len().min(distances.len());
+        let (i, extra) = words(longer.values).nth(n).unwrap_or_default();
+
+        return Err(ParseError::new(longer.offset + i, extra, "end of line")
+            .on_line(longer.line)
+            .with_hint(&format!(
+                "there are {} times but {} distances, line {} has only {n} values",
+                times.len(),
+                distances.len(),
+                shorter.line
+            )));
+    }
+
+    let races: Vec<_> = times
+        .into_iter()
+        .zip(distances)
+        .map(|(t, d)| AnyRace::new(t, d))
+        .collect();
+
+    Ok(races)
+}
+
+fn parse2(input: &str) -> Result<AnyRace, ParseError> {
+    let (ts, ds) = labels(input)?;
+
+    let time = digits(ts.values, ts.offset, ts.line)?;
+    let distance = digits(ds.values, ds.offset, ds.line)?;
+
+    Ok(AnyRace::new(time, distance))
+}
+
+fn part1(races: &[AnyRace]) -> BigUint {
+    races.iter().map(|r| r.wins()).product()
+}
+
+fn part2(race: &AnyRace) -> BigUint {
+    race.wins()
+}
+
+/// Every race of part 1 and the single race of part 2, with their winning holds.
+fn report(races: &[AnyRace], race: &AnyRace) -> Report {
+    let mut report = Report::new(&[
+        "part",
+        "race",
+        "time",
+        "record",
+        "min_hold",
+        "max_hold",
+        "wins",
+        "best_hold",
+        "best_distance",
+        "margin",
+    ]);
+
+    let numbered = races.iter().enumerate().map(|(i, r)| (Part::One, i, r));
+    for (part, i, r) in numbered.chain([(Part::Two, 0, race)]) {
+        let mut row = vec![part.into(), (i + 1).into()];
+        row.extend(r.row());
+        report.push(row);
+    }
+
+    report
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = (Vec<AnyRace>, AnyRace);
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((parse1(input)?, parse2(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&input.0)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(&input.1)
+    }
+
+    fn report(input: &Self::Input) -> Option<Report> {
+        Some(report(&input.0, &input.1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+
+    #[test]
+    fn test_race_wins() {
+        let race = |time, distance| Race { time, distance };
+        assert_eq!(4, race(7, 9).wins());
+        assert_eq!(8, race(15, 40).wins());
+        assert_eq!(9, race(30, 200).wins());
+    }
+
+    #[test]
+    fn test_race_holds() {
+        let race = |time, distance| Race { time, distance };
+        assert_eq!(Some((2, 5)), race(7, 9).holds());
+        assert_eq!(Some((11, 19)), race(30, 200).holds());
+        assert_eq!(None, race(4, 4).holds());
+        assert_eq!(None, race(0, 0).holds());
+        assert_eq!(Some((1, 1)), race(2, 0).holds());
+    }
+
+    #[test]
+    fn test_race_wins_scan() {
+        for input in ["day06/test1.txt", "day06/input.txt"] {
+            let content = load(input).unwrap();
+            let mut races = parse1(&content).unwrap();
+            races.push(parse2(&content).unwrap());
+            for race in races {
+                let AnyRace::U64(race) = race else {
+                    panic!("{race:?} does not fit into a u64");
+                };
+                assert_eq!(race.wins_scan(), race.wins(), "{race:?}");
+            }
+        }
+
+        // A small xorshift generator keeps the races reproducible.
+        let mut state: u64 = 0x2545f4914f6cdd1d;
+        let mut random = |below: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % below
+        };
+
+        for _ in 0..10000 {
+            let time = random(2000);
+            let best = (time / 2) * (time - time / 2);
+            let distance = random(best + 2);
+            let race = Race { time, distance };
+            assert_eq!(race.wins_scan(), race.wins(), "{race:?}");
+        }
+    }
+
+    #[test]
+    fn test_any_race() {
+        let race = |time: &str, distance: &str| {
+            AnyRace::new(time.parse().unwrap(), distance.parse().unwrap())
+        };
+
+        assert!(matches!(race("71530", "940200"), AnyRace::U64(_)));
+        assert!(matches!(race("4294967296", "0"), AnyRace::U128(_)));
+        assert!(matches!(
+            race("10", "4611686018427387904"),
+            AnyRace::U128(_)
+        ));
+
+        // Only holding exactly half of the race beats a distance one below the best.
+        let big = race(
+            "20000000000000000000000000000000000000000",
+            &format!("{}", BigUint::from(10u8).pow(80) - 1u8),
+        );
+        assert!(matches!(big, AnyRace::Big(_)));
+        assert_eq!(BigUint::from(1u8), big.wins());
+
+        let time = "1234567890123456789012345678901234567890";
+        let expected = time.parse::<BigUint>().unwrap() - 1u8;
+        assert_eq!(expected, race(time, "0").wins());
+
+        // The same race gives the same answer in every width.
+        for (time, distance) in [(71530u64, 940200u64), (4000000000, 3999999999000000000)] {
+            let small = Race { time, distance };
+            let wide = Race {
+                time: time as u128,
+                distance: distance as u128,
+            };
+            let big = Race {
+                time: BigUint::from(time),
+                distance: BigUint::from(distance),
+            };
+            assert_eq!(small.wins() as u128, wide.wins());
+            assert_eq!(BigUint::from(wide.wins()), big.wins());
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let content = load("day06/test1.txt").unwrap();
+        let (races, race) = (parse1(&content).unwrap(), parse2(&content).unwrap());
+        let report = report(&races, &race);
+
+        assert_eq!(4, report.rows.len());
+        let cells = report.cells();
+        assert_eq!("1 1 7 9 2 5 4 3 12 3", cells[1].join(" "));
+        assert_eq!(
+            "2 1 71530 940200 14 71516 71503 35765 1279135225 1278195025",
+            cells[4].join(" ")
+        );
+
+        let lost = AnyRace::new(4u8.into(), 4u8.into());
+        assert_eq!(vec![Json::Null, Json::Null], lost.row()[2..4]);
+        assert_eq!(Json::Null, lost.row()[7]);
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        let content = "\
+Record holder: Alice Bob Carol
+  Distance:\t9  40 200
+
+Time:      7  15   30
+";
+        assert_eq!("288", part1(&parse1(content).unwrap()).to_string());
+        assert_eq!("71503", part2(&parse2(content).unwrap()).to_string());
+
+        let e = parse1("Time: 7 15 30\nDistance: 9 40\n").unwrap_err();
+        assert_eq!((1, 12, "30".to_string()), (e.line, e.column, e.text));
+
+        let e = parse1("Time: 7 15\nDistance: 9 40 200\n").unwrap_err();
+        assert_eq!((2, 16, "200".to_string()), (e.line, e.column, e.text));
+
+        let e = parse1("Time: 7\nTime: 8\nDistance: 9\n").unwrap_err();
+        assert_eq!((2, "Time".to_string()), (e.line, e.text));
+
+        let e = parse1("Time: 7\n").unwrap_err();
+        assert_eq!((2, "a 'Distance:' line".to_string()), (e.line, e.expected));
+
+        let e = parse1("Time: 7\n7 15\n").unwrap_err();
+        assert_eq!((2, "7".to_string()), (e.line, e.text));
+    }
+
+    #[test]
+    fn test_part1() {
+        for input in ["day06/test1.txt", "day06/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part1(&parse1(&content).unwrap());
+            assert_eq!(expected(6, Part::One, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for input in ["day06/test1.txt", "day06/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part2(&parse2(&content).unwrap());
+            assert_eq!(expected(6, Part::Two, input), result.to_string());
+        }
+    }
+}
@@ -0,0 +1,435 @@
+use super::{deal, fields, show, Card, Strength, Tiebreak};
+use crate::error::number;
+use crate::json::Json;
+use crate::{ParseError, Report};
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+
+/// Group sizes of a hand, largest first, e.g. `[3, 2]` for a full house. Compared group by
+/// group, shapes rank hands of any size the way Camel Cards ranks five cards.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shape(pub Vec<u8>);
+
+impl Shape {
+    /// Groups equal cards, with the `wild` ones joining the largest group.
+    pub fn of(cards: &[Card], wild: &[Card]) -> Shape {
+        HouseRules::standard().with_wild(wild).shape(cards)
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups: Vec<String> = self.0.iter().map(|g| g.to_string()).collect();
+        write!(f, "{}", groups.join("+"))
+    }
+}
+
+/// Worth of every card, indexed by `Card as usize`, for cards ordered strongest first.
+const fn ranks(order: &[Card]) -> [u8; 13] {
+    let mut ranks = [0; 13];
+    let mut i = 0;
+    while i < order.len() {
+        ranks[order[i] as usize] = (order.len() - i) as u8;
+        i += 1;
+    }
+    ranks
+}
+
+/// Camel Cards with house rules: any cards wild, any card order and any number of cards.
+/// Parts 1 and 2 are the [`standard`](HouseRules::standard) and
+/// [`jokers`](HouseRules::jokers) rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HouseRules {
+    /// Indexed by `Card as usize`, see [`HouseRules::rank`].
+    ranks: [u8; 13],
+    /// Indexed by `Card as usize`.
+    wild: [bool; 13],
+    size: usize,
+}
+
+impl HouseRules {
+    /// Columns of [`HouseRules::report`].
+    pub const COLUMNS: [&str; 7] = [
+        "rank",
+        "hand",
+        "category",
+        "jokers_as",
+        "bid",
+        "winnings",
+        "above_previous",
+    ];
+
+    /// The rules of part 1.
+    pub const fn standard() -> Self {
+        HouseRules {
+            ranks: ranks(&Card::ALL),
+            wild: [false; 13],
+            size: 5,
+        }
+    }
+
+    /// The rules of part 2, Jacks being Jokers.
+    pub const fn jokers() -> Self {
+        use Card::*;
+
+        let mut wild = [false; 13];
+        wild[Jack as usize] = true;
+
+        HouseRules {
+            ranks: ranks(&[
+                Ace, King, Queen, Ten, Nine, Eight, Seven, Six, Five, Four, Three, Two, Jack,
+            ]),
+            wild,
+            size: 5,
+        }
+    }
+
+    pub fn with_wild(mut self, wild: &[Card]) -> Self {
+        self.wild = Card::ALL.map(|c| wild.contains(&c));
+        self
+    }
+
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Takes the card order from a string like `AKQT98765432J`, which has to name every card once.
+    pub fn with_order(mut self, order: &str) -> Result<Self, ParseError> {
+        let mut cards: Vec<Card> = vec![];
+
+        for (i, c) in order.char_indices() {
+            let card = Card::try_from(c).map_err(|e| e.shift(i))?;
+            if cards.contains(&card) {
+                return Err(ParseError::new(i, &c.to_string(), "a card not ranked yet"));
+            }
+            cards.push(card);
+        }
+
+        if cards.len() < Card::ALL.len() {
+            let missing: String = "AKQJT98765432"
+                .chars()
+                .filter(|c| !order.contains(*c))
+                .collect();
+            return Err(ParseError::new(order.len(), "", "every card")
+                .with_hint(&format!("{missing} are not ranked")));
+        }
+
+        self.ranks = ranks(&cards);
+        Ok(self)
+    }
+
+    /// Worth of `card` when comparing hands of the same shape, from 1 for the weakest card to 13
+    /// for the strongest.
+    pub fn rank(&self, card: Card) -> u8 {
+        self.ranks[card as usize]
+    }
+
+    pub fn is_wild(&self, card: Card) -> bool {
+        self.wild[card as usize]
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// How often each card occurs in `cards`, leaving out the wild ones, and how many wild ones
+    /// there are.
+    fn counts(&self, cards: &[Card]) -> ([u8; 13], u8) {
+        let mut counts = [0; 13];
+        let mut wild = 0;
+
+        for c in cards {
+            if self.is_wild(*c) {
+                wild += 1;
+            } else {
+                counts[*c as usize] += 1;
+            }
+        }
+
+        (counts, wild)
+    }
+
+    /// The strength of a hand of five cards, `None` for other sizes.
+    pub fn strength(&self, cards: &[Card]) -> Option<Strength> {
+        if cards.len() != 5 {
+            return None;
+        }
+
+        let (counts, wild) = self.counts(cards);
+        Some(Strength::from_counts(&counts, wild))
+    }
+
+    /// Group sizes, largest first and padded with zeros, which compare like shapes.
+    fn groups(&self, cards: &[Card]) -> [u8; 13] {
+        let (mut groups, wild) = self.counts(cards);
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        groups[0] += wild;
+        groups
+    }
+
+    pub fn shape(&self, cards: &[Card]) -> Shape {
+        Shape(self.groups(cards).into_iter().filter(|g| *g > 0).collect())
+    }
+
+    /// The strength of five cards, like `full house`, the shape of any other number of cards.
+    pub fn category(&self, cards: &[Card]) -> String {
+        match self.strength(cards) {
+            Some(s) => s.to_string(),
+            None => self.shape(cards).to_string(),
+        }
+    }
+
+    /// What hands are sorted by: their shape, then card by card.
+    pub fn key(&self, cards: &[Card]) -> (Shape, Vec<u8>) {
+        let ranks = cards.iter().map(|c| self.rank(*c)).collect();
+        (self.shape(cards), ranks)
+    }
+
+    /// Compares two hands like their [`key`](HouseRules::key) does, but without allocating:
+    /// five cards by strength, any other number by their groups.
+    pub fn compare(&self, a: &[Card], b: &[Card]) -> Ordering {
+        let rank = |c: &Card| self.rank(*c);
+
+        match (self.strength(a), self.strength(b)) {
+            (Some(s), Some(o)) => s.cmp(&o),
+            _ => self.groups(a).cmp(&self.groups(b)),
+        }
+        .then_with(|| a.iter().map(rank).cmp(b.iter().map(rank)))
+    }
+
+    pub fn tiebreak(&self, a: &[Card], b: &[Card]) -> Tiebreak {
+        match (self.strength(a), self.strength(b)) {
+            (Some(s), Some(o)) if s != o => return Tiebreak::Strength(s, o),
+            (Some(_), Some(_)) => {}
+            _ => {
+                let (s, o) = (self.shape(a), self.shape(b));
+                if s != o {
+                    return Tiebreak::Shape(s, o);
+                }
+            }
+        }
+
+        match a.iter().zip(b).position(|(x, y)| x != y) {
+            Some(i) => Tiebreak::Card(i, a[i], b[i]),
+            None => Tiebreak::Same,
+        }
+    }
+
+    /// Why `a` ranks where it does against `b`, without naming the hands.
+    fn reason(&self, a: &[Card], b: &[Card]) -> String {
+        let verb = match self.compare(a, b) {
+            Ordering::Greater => "beats",
+            _ => "loses to",
+        };
+
+        match self.tiebreak(a, b) {
+            Tiebreak::Strength(s, o) => format!("{s} {verb} {o}"),
+            Tiebreak::Shape(s, o) => format!("{s} {verb} {o}"),
+            Tiebreak::Card(i, x, y) => format!(
+                "both are {}, card {} decides: {x} {verb} {y}",
+                self.category(a),
+                i + 1
+            ),
+            Tiebreak::Same => "the same cards".to_string(),
+        }
+    }
+
+    /// Explains a comparison, like `KK677 > KTJJT: both are two pairs, card 2 decides: K beats T`.
+    pub fn why(&self, a: &[Card], b: &[Card]) -> String {
+        let sign = match self.compare(a, b) {
+            Ordering::Greater => '>',
+            Ordering::Less => '<',
+            Ordering::Equal => '=',
+        };
+
+        format!("{} {sign} {}: {}", show(a), show(b), self.reason(a, b))
+    }
+
+    /// What the wild cards of `cards` are best used as: all of them join the largest group,
+    /// the strongest one of those if there are several. `None` without wild cards. For the
+    /// Jokers of part 2 this is what [`Hand::best`](super::Hand::best) finds by trying.
+    pub fn substitute(&self, cards: &[Card]) -> Option<Vec<Card>> {
+        if !cards.iter().any(|c| self.is_wild(*c)) {
+            return None;
+        }
+
+        let count = |card: Card| cards.iter().filter(|c| **c == card).count();
+        let best = Card::ALL
+            .into_iter()
+            .filter(|c| !self.is_wild(*c))
+            .max_by_key(|c| (count(*c), self.rank(*c)))?;
+
+        Some(
+            cards
+                .iter()
+                .map(|c| if self.is_wild(*c) { best } else { *c })
+                .collect(),
+        )
+    }
+
+    /// Reads `<hand> <bid>` lines of hands with `size` cards.
+    pub fn parse(&self, s: &str) -> Result<Vec<(Vec<Card>, u64)>, ParseError> {
+        s.lines()
+            .enumerate()
+            .map(|(i, line)| self.parse_line(line).map_err(|e| e.on_line(i + 1)))
+            .collect()
+    }
+
+    fn parse_line(&self, line: &str) -> Result<(Vec<Card>, u64), ParseError> {
+        let ((h, hand), (b, bid)) = fields(line)?;
+        let cards = deal(hand, self.size).map_err(|e| e.shift(h))?;
+        Ok((cards, number(bid, b)?))
+    }
+
+    /// Hands from the weakest, rank 1, to the strongest.
+    pub fn ranked<'a>(&self, hands: &'a [(Vec<Card>, u64)]) -> Vec<&'a (Vec<Card>, u64)> {
+        let mut hands: Vec<&(Vec<Card>, u64)> = hands.iter().collect();
+        hands.sort_by(|(a, _), (b, _)| self.compare(a, b));
+        hands
+    }
+
+    /// Total winnings of a tournament under these rules, like part 1 and 2.
+    pub fn winnings(&self, hands: &[(Vec<Card>, u64)]) -> u64 {
+        self.ranked(hands)
+            .iter()
+            .zip(1..)
+            .map(|((_, v), i)| v * i)
+            .sum()
+    }
+
+    /// One row of [`COLUMNS`](HouseRules::COLUMNS) per hand in the order of their rank, each
+    /// with why it ranks above the one before.
+    pub fn rows(&self, hands: &[(Vec<Card>, u64)]) -> Vec<Vec<Json>> {
+        let hands = self.ranked(hands);
+
+        hands
+            .iter()
+            .enumerate()
+            .map(|(i, (cards, bid))| {
+                let rank = i as u64 + 1;
+                let why = i.checked_sub(1).map(|j| self.reason(cards, &hands[j].0));
+
+                vec![
+                    Json::number(rank),
+                    show(cards).into(),
+                    self.category(cards).into(),
+                    self.substitute(cards).map(|c| show(&c)).into(),
+                    Json::number(bid),
+                    Json::number(rank * bid),
+                    why.into(),
+                ]
+            })
+            .collect()
+    }
+
+    /// The ranked list of a tournament under these rules.
+    pub fn report(&self, hands: &[(Vec<Card>, u64)]) -> Report {
+        let mut report = Report::new(&Self::COLUMNS);
+        for row in self.rows(hands) {
+            report.push(row);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::odds::{odds_with, Deck};
+    use super::super::{parse, part1, part2};
+    use super::*;
+    use crate::input::load;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_shape() {
+        // Shapes of five cards order them the same way strengths do.
+        let shapes: [&[u8]; 7] = [
+            &[1, 1, 1, 1, 1],
+            &[2, 1, 1, 1],
+            &[2, 2, 1],
+            &[3, 1, 1],
+            &[3, 2],
+            &[4, 1],
+            &[5],
+        ];
+        let cards = [Card::Ace, Card::King, Card::Queen, Card::Jack, Card::Ten];
+        for hand in (0..5).map(|_| cards).multi_cartesian_product() {
+            for rules in [HouseRules::standard(), HouseRules::jokers()] {
+                let strength = rules.strength(&hand).unwrap();
+                assert_eq!(shapes[strength as usize], rules.shape(&hand).0, "{hand:?}");
+            }
+        }
+
+        assert_eq!("6", Shape::of(&[Card::Two; 6], &[]).to_string());
+        assert_eq!(
+            "4+2",
+            Shape::of(&deal("22JQQ2", 6).unwrap(), &[Card::Jack]).to_string()
+        );
+        assert!(Shape(vec![3, 3]) > Shape(vec![3, 2, 1]));
+        assert!(Shape(vec![2, 2, 2]) > Shape(vec![2, 2, 1, 1]));
+    }
+
+    #[test]
+    fn test_winnings() {
+        let content = load("day07/input.txt").unwrap();
+        assert_eq!(
+            part1(&parse(&content).unwrap()),
+            HouseRules::standard().winnings(&HouseRules::standard().parse(&content).unwrap())
+        );
+
+        let jokers = HouseRules::standard()
+            .with_wild(&[Card::Jack])
+            .with_order("AKQT98765432J")
+            .unwrap();
+        assert_eq!(HouseRules::jokers(), jokers);
+        assert_eq!(
+            part2(&parse(&content).unwrap()),
+            jokers.winnings(&jokers.parse(&content).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_house_rules() {
+        // Four card hands with Twos wild and Aces lowest: AAA2 is four of a kind, KKQQ two pairs
+        // and K2QT one pair.
+        let rules = HouseRules::standard()
+            .with_size(4)
+            .with_wild(&[Card::Two])
+            .with_order("KQJT98765432A")
+            .unwrap();
+        let hands = rules.parse("KKQQ 1\nAAA2 10\nK2QT 100\n").unwrap();
+        assert_eq!(3 * 10 + 2 + 100, rules.winnings(&hands));
+        assert_eq!((1, 13), (rules.rank(Card::Ace), rules.rank(Card::King)));
+
+        let cells = rules.report(&hands).cells();
+        assert_eq!(
+            vec!["3", "AAA2", "4", "AAAA", "10", "30", "4 beats 2+2"],
+            cells[3]
+        );
+        assert_eq!(
+            "KKQQ > K2QT: 2+2 beats 2+1+1",
+            rules.why(&hands[0].0, &hands[2].0)
+        );
+
+        let partial = deal("222", 3).unwrap();
+        let odds = odds_with(&rules, &partial, &Deck::full().without(&partial));
+        assert_eq!(vec![(Shape(vec![4]), 1.0)], odds.distribution());
+
+        let e = rules.parse("KKQQ 1\nKKQQQ 2\n").unwrap_err();
+        assert_eq!((2, 5, "Q".to_string()), (e.line, e.column, e.text));
+        assert_eq!(
+            Some("a hand has exactly four cards, found 5".to_string()),
+            e.hint
+        );
+
+        let e = HouseRules::standard()
+            .with_order("AKQJT98765432A")
+            .unwrap_err();
+        assert_eq!((14, "A".to_string()), (e.column, e.text));
+        let e = HouseRules::standard()
+            .with_order("AKQJT9876543")
+            .unwrap_err();
+        assert_eq!(Some("2 are not ranked".to_string()), e.hint);
+    }
+}
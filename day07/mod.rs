@@ -0,0 +1,558 @@
+use crate::error::{number, words};
+use crate::{ParseError, Part, Report, Solution};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+mod house;
+pub use house::{HouseRules, Shape};
+pub mod odds;
+pub mod poker;
+
+#[derive(Debug, Eq, Hash, Copy, Clone, PartialEq)]
+pub enum Card {
+    Ace,
+    King,
+    Queen,
+    Jack,
+    Ten,
+    Nine,
+    Eight,
+    Seven,
+    Six,
+    Five,
+    Four,
+    Three,
+    Two,
+}
+
+/// Five cards in the order they were dealt. How hands compare depends on the [`Rules`], see
+/// [`Ranked`].
+#[derive(Debug, Eq, Hash, Copy, Clone, PartialEq)]
+pub struct Hand(Card, Card, Card, Card, Card);
+
+/// Categories of hands from weakest to strongest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPairs = 2,
+    ThreeOfKind = 3,
+    FullHouse = 4,
+    FourOfKind = 5,
+    FiveOfKind = 6,
+}
+
+impl Strength {
+    pub const ALL: [Strength; 7] = [
+        Strength::HighCard,
+        Strength::OnePair,
+        Strength::TwoPairs,
+        Strength::ThreeOfKind,
+        Strength::FullHouse,
+        Strength::FourOfKind,
+        Strength::FiveOfKind,
+    ];
+
+    /// Classifies a hand by how often each card occurs in it. Wild cards always do best by
+    /// joining the largest group.
+    pub fn from_counts(counts: &[u8], wild: u8) -> Strength {
+        let (mut first, mut second) = (0, 0);
+        for c in counts {
+            if *c > first {
+                second = first;
+                first = *c;
+            } else if *c > second {
+                second = *c;
+            }
+        }
+
+        match (first + wild, second) {
+            (5, _) => Strength::FiveOfKind,
+            (4, _) => Strength::FourOfKind,
+            (3, 2) => Strength::FullHouse,
+            (3, _) => Strength::ThreeOfKind,
+            (2, 2) => Strength::TwoPairs,
+            (2, _) => Strength::OnePair,
+            _ => Strength::HighCard,
+        }
+    }
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Strength::HighCard => "high card",
+            Strength::OnePair => "one pair",
+            Strength::TwoPairs => "two pairs",
+            Strength::ThreeOfKind => "three of a kind",
+            Strength::FullHouse => "full house",
+            Strength::FourOfKind => "four of a kind",
+            Strength::FiveOfKind => "five of a kind",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", "AKQJT98765432".as_bytes()[*self as usize] as char)
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", show(&self.cards()))
+    }
+}
+
+/// Writes cards the way they are dealt, like `32T3K`.
+fn show(cards: &[Card]) -> String {
+    cards.iter().map(|c| c.to_string()).collect()
+}
+
+impl TryFrom<char> for Card {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            'A' => Card::Ace,
+            'K' => Card::King,
+            'Q' => Card::Queen,
+            'J' => Card::Jack,
+            'T' => Card::Ten,
+            '9' => Card::Nine,
+            '8' => Card::Eight,
+            '7' => Card::Seven,
+            '6' => Card::Six,
+            '5' => Card::Five,
+            '4' => Card::Four,
+            '3' => Card::Three,
+            '2' => Card::Two,
+            x => {
+                return Err(
+                    ParseError::new(0, &x.to_string(), "a card").with_hint(&format!(
+                        "unknown card '{x}', expected one of AKQJT98765432"
+                    )),
+                )
+            }
+        })
+    }
+}
+
+impl Card {
+    pub const ALL: [Card; 13] = [
+        Card::Ace,
+        Card::King,
+        Card::Queen,
+        Card::Jack,
+        Card::Ten,
+        Card::Nine,
+        Card::Eight,
+        Card::Seven,
+        Card::Six,
+        Card::Five,
+        Card::Four,
+        Card::Three,
+        Card::Two,
+    ];
+}
+
+/// House rules fixed at compile time, so that hands can be ranked by type, see [`Ranked`].
+pub trait Rules {
+    const HOUSE: HouseRules;
+
+    /// Worth of `card` when comparing hands of the same strength, higher is better.
+    fn rank(card: Card) -> u8 {
+        Self::HOUSE.rank(card)
+    }
+
+    fn strength(hand: &Hand) -> Strength {
+        Self::HOUSE
+            .strength(&hand.cards())
+            .expect("a hand has five cards")
+    }
+}
+
+/// Part 1: Jacks are Jacks.
+#[derive(Debug, Copy, Clone)]
+pub struct Standard;
+
+/// Part 2: Jacks are Jokers, the weakest card on their own, but wild when classifying a hand.
+#[derive(Debug, Copy, Clone)]
+pub struct Jokers;
+
+impl Rules for Standard {
+    const HOUSE: HouseRules = HouseRules::standard();
+}
+
+impl Rules for Jokers {
+    const HOUSE: HouseRules = HouseRules::jokers();
+}
+
+/// A hand that is ordered by the rules `R`: by strength first, then card by card.
+pub struct Ranked<R: Rules>(pub Hand, PhantomData<R>);
+
+/// What decides between two hands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tiebreak {
+    Strength(Strength, Strength),
+    /// Hands of any other size than five differ in shape.
+    Shape(Shape, Shape),
+    /// Both are equally strong, so the first card that differs decides, counting from 0.
+    Card(usize, Card, Card),
+    Same,
+}
+
+impl<R: Rules> Ranked<R> {
+    pub fn new(hand: Hand) -> Self {
+        Ranked(hand, PhantomData)
+    }
+
+    pub fn tiebreak(&self, other: &Self) -> Tiebreak {
+        R::HOUSE.tiebreak(&self.0.cards(), &other.0.cards())
+    }
+
+    /// Explains a comparison, like `KK677 > KTJJT: both are two pairs, card 2 decides: K beats T`.
+    pub fn why(&self, other: &Self) -> String {
+        R::HOUSE.why(&self.0.cards(), &other.0.cards())
+    }
+}
+
+impl<R: Rules> Clone for Ranked<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: Rules> Copy for Ranked<R> {}
+
+impl<R: Rules> fmt::Debug for Ranked<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Ranked").field(&self.0).finish()
+    }
+}
+
+impl<R: Rules> Ord for Ranked<R> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.0.cards(), other.0.cards());
+
+        R::strength(&self.0)
+            .cmp(&R::strength(&other.0))
+            .then_with(|| a.map(R::rank).cmp(&b.map(R::rank)))
+    }
+}
+
+impl<R: Rules> PartialOrd for Ranked<R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: Rules> PartialEq for Ranked<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<R: Rules> Eq for Ranked<R> {}
+
+/// Parses a hand of exactly `size` cards.
+fn deal(s: &str, size: usize) -> Result<Vec<Card>, ParseError> {
+    let cards: Vec<Card> = s
+        .char_indices()
+        .map(|(i, c)| Card::try_from(c).map_err(|e| e.shift(i)))
+        .collect::<Result<_, _>>()?;
+
+    let words = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let count = words.get(size).map_or(size.to_string(), |w| w.to_string());
+    let hint = format!("a hand has exactly {count} cards, found {}", cards.len());
+
+    if cards.len() > size {
+        return Err(ParseError::new(size, &s[size..], "end of hand").with_hint(&hint));
+    }
+
+    if cards.len() < size {
+        return Err(ParseError::new(s.len(), "", &format!("{count} cards")).with_hint(&hint));
+    }
+
+    Ok(cards)
+}
+
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [a, b, c, d, e] = deal(s, 5)?[..] else {
+            unreachable!("deal checks the number of cards");
+        };
+
+        Ok(Hand(a, b, c, d, e))
+    }
+}
+
+impl Hand {
+    fn cards(&self) -> [Card; 5] {
+        [self.0, self.1, self.2, self.3, self.4]
+    }
+
+    fn change(&self, i: usize, c: Card) -> Hand {
+        let mut hand = *self;
+        match i {
+            0 => hand.0 = c,
+            1 => hand.1 = c,
+            2 => hand.2 = c,
+            3 => hand.3 = c,
+            4 => hand.4 = c,
+            _ => panic!("Can not set {i}'th Card in Hand"),
+        }
+        hand
+    }
+
+    /// Every hand the Jacks could stand for as Jokers, the first Jack varying slowest.
+    fn gen_hands(&self) -> Vec<Hand> {
+        let Some(i) = self.cards().iter().position(|c| *c == Card::Jack) else {
+            return vec![*self];
+        };
+
+        Card::ALL
+            .iter()
+            .filter(|c| **c != Card::Jack)
+            .flat_map(|c| self.change(i, *c).gen_hands())
+            .collect()
+    }
+
+    /// What the Jokers are best used as, trying every possibility. [`Jokers::strength`] gets
+    /// the same strength without trying.
+    pub fn best(&self) -> Hand {
+        self.gen_hands()
+            .into_iter()
+            .max_by_key(|h| Ranked::<Standard>::new(*h))
+            .unwrap()
+    }
+}
+
+/// Total winnings: every bid times the rank of its hand under the rules `R`.
+fn winnings<R: Rules>(h: &[(Hand, u64)]) -> u64 {
+    let mut hands = h.to_vec();
+    hands.sort_by_key(|(h, _)| Ranked::<R>::new(*h));
+    hands.iter().zip(1..).map(|((_, v), i)| v * i).sum()
+}
+
+/// The hands as plain cards, which is what [`HouseRules`] deals with.
+fn dealt(h: &[(Hand, u64)]) -> Vec<(Vec<Card>, u64)> {
+    h.iter().map(|(h, v)| (h.cards().to_vec(), *v)).collect()
+}
+
+fn report(h: &[(Hand, u64)]) -> Report {
+    let columns: Vec<&str> = iter::once("part").chain(HouseRules::COLUMNS).collect();
+    let mut report = Report::new(&columns);
+
+    for (part, rules) in [(Part::One, Standard::HOUSE), (Part::Two, Jokers::HOUSE)] {
+        for row in rules.rows(&dealt(h)) {
+            report.push(iter::once(part.into()).chain(row).collect());
+        }
+    }
+
+    report
+}
+
+fn part1(h: &[(Hand, u64)]) -> u64 {
+    winnings::<Standard>(h)
+}
+
+fn part2(h: &[(Hand, u64)]) -> u64 {
+    winnings::<Jokers>(h)
+}
+
+/// A word of a line with its offset.
+type Word<'a> = (usize, &'a str);
+
+/// Splits a line into its hand and its bid.
+fn fields(line: &str) -> Result<(Word<'_>, Word<'_>), ParseError> {
+    let fields: Vec<(usize, &str)> = words(line).collect();
+
+    if let Some((o, w)) = fields.get(2) {
+        return Err(ParseError::new(*o, w, "end of line"));
+    }
+
+    let [hand, bid] = fields[..] else {
+        return Err(ParseError::new(line.len(), "", "'<hand> <bid>'"));
+    };
+
+    Ok((hand, bid))
+}
+
+fn parse_line(line: &str) -> Result<(Hand, u64), ParseError> {
+    let ((h, hand), (b, bid)) = fields(line)?;
+
+    let hand: Hand = hand.parse().map_err(|e: ParseError| e.shift(h))?;
+    Ok((hand, number(bid, b)?))
+}
+
+fn parse(s: &str) -> Result<Vec<(Hand, u64)>, ParseError> {
+    s.lines()
+        .enumerate()
+        .map(|(i, l)| parse_line(l).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<(Hand, u64)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+
+    fn report(input: &Self::Input) -> Option<Report> {
+        Some(report(input))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+    use crate::json::Json;
+    use crate::Part;
+    use itertools::Itertools;
+    use std::collections::{BTreeSet, BinaryHeap};
+
+    #[test]
+    fn test_part1() {
+        for input in ["day07/test1.txt", "day07/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part1(&parse(&content).unwrap());
+            assert_eq!(expected(7, Part::One, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for input in ["day07/test1.txt", "day07/input.txt"] {
+            let content = load(input).unwrap();
+            let result = part2(&parse(&content).unwrap());
+            assert_eq!(expected(7, Part::Two, input), result.to_string());
+        }
+    }
+
+    #[test]
+    fn test_joker_strength() {
+        for cards in (0..5).map(|_| Card::ALL).multi_cartesian_product() {
+            let hand = Hand(cards[0], cards[1], cards[2], cards[3], cards[4]);
+            let best = hand.best();
+            assert_eq!(
+                Standard::strength(&best),
+                Jokers::strength(&hand),
+                "{hand:?}"
+            );
+
+            // The report shows the same substitution without trying every one.
+            let substitute = Jokers::HOUSE.substitute(&cards).unwrap_or(cards);
+            assert_eq!(best.cards().to_vec(), substitute, "{hand:?}");
+        }
+    }
+
+    #[test]
+    fn test_ranked() {
+        let hands: Vec<Hand> = ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"]
+            .iter()
+            .map(|h| h.parse().unwrap())
+            .collect();
+        let order = |expected: [usize; 5]| expected.map(|i| hands[i]).to_vec();
+
+        let mut sorted = hands.clone();
+        sorted.sort_by_key(|h| Ranked::<Standard>::new(*h));
+        assert_eq!(order([0, 3, 2, 1, 4]), sorted);
+
+        let set: BTreeSet<Ranked<Jokers>> = hands.iter().map(|h| Ranked::new(*h)).collect();
+        let sorted: Vec<Hand> = set.into_iter().map(|r| r.0).collect();
+        assert_eq!(order([0, 2, 1, 4, 3]), sorted);
+
+        let mut heap: BinaryHeap<Ranked<Jokers>> = hands.iter().map(|h| Ranked::new(*h)).collect();
+        assert_eq!(Some(hands[3]), heap.pop().map(|r| r.0));
+
+        // Equally strong under both rules, so the first card decides: Jack over Two, but Joker
+        // under Two.
+        let (jack, two): (Hand, Hand) = ("J2345".parse().unwrap(), "2J345".parse().unwrap());
+        assert!(Ranked::<Standard>::new(jack) > Ranked::new(two));
+        assert!(Ranked::<Jokers>::new(jack) < Ranked::new(two));
+    }
+
+    #[test]
+    fn test_why() {
+        let hand = |s: &str| s.parse::<Hand>().unwrap();
+        let standard = |s| Ranked::<Standard>::new(hand(s));
+        let jokers = |s| Ranked::<Jokers>::new(hand(s));
+
+        assert_eq!(
+            "KK677 > KTJJT: both are two pairs, card 2 decides: K beats T",
+            standard("KK677").why(&standard("KTJJT"))
+        );
+        assert_eq!(
+            "KK677 < KTJJT: two pairs loses to four of a kind",
+            jokers("KK677").why(&jokers("KTJJT"))
+        );
+        assert_eq!(
+            Tiebreak::Card(0, Card::Ten, Card::Queen),
+            jokers("T55J5").tiebreak(&jokers("QQQJA"))
+        );
+        assert_eq!(
+            "32T3K = 32T3K: the same cards",
+            jokers("32T3K").why(&jokers("32T3K"))
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let content = load("day07/test1.txt").unwrap();
+        let report = report(&parse(&content).unwrap());
+        let cells = report.cells();
+
+        assert_eq!(11, cells.len());
+        assert_eq!("1 1 32T3K one pair - 765 765 -", cells[1].join(" "));
+        assert_eq!(
+            "2 5 KTJJT four of a kind KTTTT 220 1100 both are four of a kind, card 1 decides: K beats Q",
+            cells[10].join(" ")
+        );
+
+        let winnings = |part: i128| -> i128 {
+            report
+                .rows
+                .iter()
+                .filter(|r| r[0] == Json::Int(part))
+                .map(|r| match r[6] {
+                    Json::Int(w) => w,
+                    _ => unreachable!(),
+                })
+                .sum()
+        };
+        assert_eq!(6440, winnings(1));
+        assert_eq!(5905, winnings(2));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("32T3K 765\nT5XJ5 684").unwrap_err();
+        assert_eq!((2, 3, "X"), (e.line, e.column, e.text.as_str()));
+    }
+}
@@ -0,0 +1,215 @@
+use super::{Card, HouseRules, Rules, Shape, Strength};
+use std::collections::BTreeMap;
+
+/// The cards left to draw from, by how many of each there are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck(pub [u8; 13]);
+
+impl Deck {
+    /// Four of every card.
+    pub fn full() -> Deck {
+        Deck([4; 13])
+    }
+
+    /// The deck after `cards` have been taken out of it.
+    pub fn without(mut self, cards: &[Card]) -> Deck {
+        for c in cards {
+            self.0[*c as usize] = self.0[*c as usize].saturating_sub(1);
+        }
+        self
+    }
+
+    pub fn len(&self) -> u64 {
+        self.0.iter().map(|n| *n as u64).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// In how many of all the ways to complete a hand it ends up as each `K`: a [`Strength`] for
+/// five cards, a [`Shape`] for hands of any size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Odds<K = Strength> {
+    pub ways: BTreeMap<K, u64>,
+    pub total: u64,
+}
+
+impl<K: Ord + Clone> Odds<K> {
+    fn new() -> Odds<K> {
+        Odds {
+            ways: BTreeMap::new(),
+            total: 0,
+        }
+    }
+
+    /// 0 for everything when the deck has too few cards to complete the hand.
+    pub fn probability(&self, outcome: &K) -> f64 {
+        match self.total {
+            0 => 0.0,
+            total => self.ways.get(outcome).copied().unwrap_or(0) as f64 / total as f64,
+        }
+    }
+
+    /// Everything that can happen with its probability, weakest first. Empty when the deck
+    /// has too few cards to complete the hand.
+    pub fn distribution(&self) -> Vec<(K, f64)> {
+        self.ways
+            .keys()
+            .map(|k| (k.clone(), self.probability(k)))
+            .collect()
+    }
+}
+
+fn choose(n: u64, k: u64) -> u64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Exact odds of each strength under the rules `R` when `partial` is filled up to five cards
+/// with cards drawn from `deck`.
+pub fn odds<R: Rules>(partial: &[Card], deck: &Deck) -> Odds {
+    assert!(partial.len() <= 5, "a hand has at most 5 cards");
+
+    let classify = |cards: &[Card]| R::HOUSE.strength(cards).expect("five cards are drawn");
+    let mut odds = Odds::new();
+    draw(5, &classify, &mut partial.to_vec(), deck, 0, 1, &mut odds);
+    odds
+}
+
+/// Exact odds of each shape under `rules` when `partial` is filled up to a full hand with cards
+/// drawn from `deck`. Every set of drawn cards is equally likely, so instead of going through
+/// all of them this goes through how many of each card are drawn, weighted by in how many ways
+/// the deck allows that.
+pub fn odds_with(rules: &HouseRules, partial: &[Card], deck: &Deck) -> Odds<Shape> {
+    assert!(
+        partial.len() <= rules.size(),
+        "a hand has at most {} cards",
+        rules.size()
+    );
+
+    let classify = |cards: &[Card]| rules.shape(cards);
+    let mut odds = Odds::new();
+    draw(
+        rules.size(),
+        &classify,
+        &mut partial.to_vec(),
+        deck,
+        0,
+        1,
+        &mut odds,
+    );
+    odds
+}
+
+/// Draws the cards of `deck` from index `from` on until `cards` has `size` of them, counting
+/// every outcome `classify` tells apart.
+fn draw<K: Ord + Clone>(
+    size: usize,
+    classify: &impl Fn(&[Card]) -> K,
+    cards: &mut Vec<Card>,
+    deck: &Deck,
+    from: usize,
+    ways: u64,
+    odds: &mut Odds<K>,
+) {
+    let left = size - cards.len();
+    if left == 0 {
+        *odds.ways.entry(classify(cards)).or_default() += ways;
+        odds.total += ways;
+        return;
+    }
+
+    for i in from..Card::ALL.len() {
+        for k in 1..=left.min(deck.0[i] as usize) {
+            cards.extend(std::iter::repeat_n(Card::ALL[i], k));
+            draw(
+                size,
+                classify,
+                cards,
+                deck,
+                i + 1,
+                ways * choose(deck.0[i] as u64, k as u64),
+                odds,
+            );
+            cards.truncate(cards.len() - k);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{Hand, Jokers, Standard};
+    use super::*;
+    use itertools::Itertools;
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.chars().map(|c| Card::try_from(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_certain() {
+        let full = odds::<Standard>(&cards("KKQQ2"), &Deck::full());
+        assert_eq!(vec![(Strength::TwoPairs, 1.0)], full.distribution());
+
+        let partial = cards("JJJJ");
+        let deck = Deck::full().without(&partial);
+        let five = odds::<Jokers>(&partial, &deck);
+        assert_eq!(vec![(Strength::FiveOfKind, 1.0)], five.distribution());
+        assert_eq!(48, five.total);
+
+        let four = odds::<Standard>(&partial, &deck);
+        assert_eq!(vec![(Strength::FourOfKind, 1.0)], four.distribution());
+    }
+
+    #[test]
+    fn test_small_deck() {
+        // One card left, but three are needed.
+        let deck = Deck::full()
+            .without(&Card::ALL[1..].repeat(4))
+            .without(&[Card::Ace; 3]);
+        assert_eq!(1, deck.len());
+
+        let odds = odds::<Standard>(&cards("KK"), &deck);
+        assert_eq!(0, odds.total);
+        assert!(odds.distribution().is_empty());
+        assert_eq!(0.0, odds.probability(&Strength::OnePair));
+    }
+
+    #[test]
+    fn test_enumeration() {
+        for partial in ["KQ", "J7", "JJ"] {
+            let partial = cards(partial);
+            let deck = Deck::full().without(&partial);
+            let pile: Vec<Card> = Card::ALL
+                .iter()
+                .flat_map(|c| vec![*c; deck.0[*c as usize] as usize])
+                .collect();
+
+            let mut standard = BTreeMap::new();
+            let mut jokers = BTreeMap::new();
+            for drawn in pile.iter().combinations(3) {
+                let [a, b] = partial[..] else { unreachable!() };
+                let hand = Hand(a, b, *drawn[0], *drawn[1], *drawn[2]);
+                *standard.entry(Standard::strength(&hand)).or_default() += 1;
+                *jokers.entry(Jokers::strength(&hand)).or_default() += 1;
+            }
+
+            let total = choose(deck.len(), 3);
+            assert_eq!(
+                Odds {
+                    ways: standard,
+                    total
+                },
+                odds::<Standard>(&partial, &deck)
+            );
+            assert_eq!(
+                Odds {
+                    ways: jokers,
+                    total
+                },
+                odds::<Jokers>(&partial, &deck)
+            );
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use super::{Card, Rules, Standard};
+use crate::error::{number, words};
+use crate::ParseError;
+use std::str::FromStr;
+
+#[derive(Debug, Eq, Hash, Copy, Clone, PartialEq)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+/// A card of a standard deck, written like `AH` or `9S`.
+#[derive(Debug, Eq, Hash, Copy, Clone, PartialEq)]
+pub struct PokerCard {
+    pub card: Card,
+    pub suit: Suit,
+}
+
+/// Poker hands from weakest to strongest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPairs,
+    ThreeOfKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfKind,
+    StraightFlush,
+}
+
+impl FromStr for PokerCard {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(c), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(ParseError::new(0, s, "a card like 'AH'")
+                .with_hint("a card is its rank followed by its suit"));
+        };
+
+        let card = Card::try_from(c)?;
+        let suit = match suit {
+            'C' => Suit::Clubs,
+            'D' => Suit::Diamonds,
+            'H' => Suit::Hearts,
+            'S' => Suit::Spades,
+            x => {
+                return Err(ParseError::new(1, &x.to_string(), "a suit")
+                    .with_hint("suits are C, D, H and S"))
+            }
+        };
+
+        Ok(PokerCard { card, suit })
+    }
+}
+
+/// Five different cards of a standard deck.
+#[derive(Debug, Eq, Hash, Copy, Clone, PartialEq)]
+pub struct PokerHand(pub [PokerCard; 5]);
+
+impl PokerHand {
+    /// Ranks of the cards in the order they decide ties: larger groups first, higher ranks
+    /// first within groups of the same size.
+    fn ranks(&self) -> [u8; 5] {
+        let mut counts = [0u8; 14];
+        for c in self.0 {
+            counts[Standard::rank(c.card) as usize] += 1;
+        }
+
+        let mut ranks = self.0.map(|c| Standard::rank(c.card));
+        ranks.sort_by_key(|r| std::cmp::Reverse((counts[*r as usize], *r)));
+        ranks
+    }
+
+    /// The highest rank of a straight, where an Ace can also be low.
+    fn straight(ranks: &[u8; 5]) -> Option<u8> {
+        let ace = Standard::rank(Card::Ace);
+        let five = Standard::rank(Card::Five);
+
+        if ranks.windows(2).all(|w| w[0] == w[1] + 1) {
+            Some(ranks[0])
+        } else if ranks[0] == ace
+            && ranks[1] == five
+            && ranks[1..].windows(2).all(|w| w[0] == w[1] + 1)
+        {
+            Some(five)
+        } else {
+            None
+        }
+    }
+
+    pub fn category(&self) -> Category {
+        self.key().0
+    }
+
+    /// What hands are ranked by: their category, then the kickers in order.
+    pub fn key(&self) -> (Category, [u8; 5]) {
+        let ranks = self.ranks();
+        let flush = self.0.iter().all(|c| c.suit == self.0[0].suit);
+
+        if let Some(high) = Self::straight(&ranks) {
+            let category = if flush {
+                Category::StraightFlush
+            } else {
+                Category::Straight
+            };
+            return (category, [high, 0, 0, 0, 0]);
+        }
+
+        let same = |i: usize, j: usize| ranks[i] == ranks[j];
+        let category = if same(0, 3) {
+            Category::FourOfKind
+        } else if same(0, 2) && same(3, 4) {
+            Category::FullHouse
+        } else if flush {
+            Category::Flush
+        } else if same(0, 2) {
+            Category::ThreeOfKind
+        } else if same(0, 1) && same(2, 3) {
+            Category::TwoPairs
+        } else if same(0, 1) {
+            Category::OnePair
+        } else {
+            Category::HighCard
+        };
+
+        (category, ranks)
+    }
+}
+
+impl FromStr for PokerHand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards: Vec<PokerCard> = vec![];
+
+        for (i, w) in words(s) {
+            let card: PokerCard = w.parse().map_err(|e: ParseError| e.shift(i))?;
+            if cards.contains(&card) {
+                return Err(ParseError::new(i, w, "a card not dealt yet")
+                    .with_hint("a hand is dealt from a single deck"));
+            }
+            if cards.len() == 5 {
+                return Err(ParseError::new(i, w, "end of hand")
+                    .with_hint("a poker hand has exactly five cards"));
+            }
+            cards.push(card);
+        }
+
+        let cards: [PokerCard; 5] = cards.try_into().map_err(|c: Vec<PokerCard>| {
+            ParseError::new(s.len(), "", "five cards").with_hint(&format!(
+                "a poker hand has exactly five cards, found {}",
+                c.len()
+            ))
+        })?;
+
+        Ok(PokerHand(cards))
+    }
+}
+
+/// Reads lines of five cards followed by a bid, like `AH KD 9S 9C 2H 765`.
+pub fn parse(s: &str) -> Result<Vec<(PokerHand, u64)>, ParseError> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+fn parse_line(line: &str) -> Result<(PokerHand, u64), ParseError> {
+    let Some((b, bid)) = words(line).last() else {
+        return Err(ParseError::new(0, "", "'<cards> <bid>'"));
+    };
+
+    let hand = line[..b].parse()?;
+    Ok((hand, number(bid, b)?))
+}
+
+/// Total winnings of the hands, ranked by poker rules like part 1 ranks Camel Cards.
+pub fn winnings(hands: &[(PokerHand, u64)]) -> u64 {
+    let mut hands = hands.to_vec();
+    hands.sort_by_key(|(h, _)| h.key());
+    hands.iter().zip(1..).map(|((_, v), i)| v * i).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hand(s: &str) -> PokerHand {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_category() {
+        let categories = [
+            ("AH KD 9S 9C 2H", Category::OnePair),
+            ("AH KH 9H 7H 2H", Category::Flush),
+            ("5D 4C 3S 2H AH", Category::Straight),
+            ("TS JS QS KS AS", Category::StraightFlush),
+            ("9S 9C 9H 2D 2H", Category::FullHouse),
+            ("9S 9C 9H 9D 2H", Category::FourOfKind),
+            ("9S 9C 2H 3D 3H", Category::TwoPairs),
+            ("9S 9C 9H 3D 2H", Category::ThreeOfKind),
+            ("AH KD QS JC 9H", Category::HighCard),
+        ];
+
+        for (cards, category) in categories {
+            assert_eq!(category, hand(cards).category(), "{cards}");
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        // Kickers decide between equal pairs, the wheel is the lowest straight.
+        assert!(hand("9H 9D AS 3C 2H").key() > hand("9S 9C KH QD JH").key());
+        assert!(hand("6H 5D 4S 3C 2H").key() > hand("5D 4C 3S 2H AH").key());
+        assert!(hand("QH QD 2S 2C AH").key() > hand("JS JC TH TD KH").key());
+        assert_eq!(hand("AH KD 9S 9C 2H").key(), hand("AS KC 9H 9D 2S").key());
+    }
+
+    #[test]
+    fn test_winnings() {
+        let hands = parse("AH KD 9S 9C 2H 5\n5D 4C 3S 2H AH 20\nKH KD KS 2C 2H 100\n").unwrap();
+        assert_eq!(5 + 2 * 20 + 3 * 100, winnings(&hands));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("AH KD 9S 9C 2H 5\nAH KD 9X 9C 2H 7\n").unwrap_err();
+        assert_eq!((2, 8, "X"), (e.line, e.column, e.text.as_str()));
+
+        let e = parse("AH KD AH 9C 2H 5\n").unwrap_err();
+        assert_eq!((1, 7, "AH"), (e.line, e.column, e.text.as_str()));
+
+        let e = parse("AH KD 9S 9C 5\n").unwrap_err();
+        assert_eq!(
+            (1, 13, "five cards"),
+            (e.line, e.column, e.expected.as_str())
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{input, Part};
+use std::fs;
+
+/// Location of the checked-in expected answers, relative to the repository root.
+pub const MANIFEST: &str = "./answers.txt";
+
+/// One line of the manifest: running `part` of `day` on `input` must give `answer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Parses a manifest with one `<day> <part> <input> <answer>` entry per line, see
+/// [`input::records`].
+pub fn parse(s: &str) -> Result<Vec<Expected>, String> {
+    input::records(s, "<day> <part> <input> <answer>")?
+        .into_iter()
+        .map(|(i, [day, part, input, answer])| {
+            Ok(Expected {
+                day: day
+                    .parse()
+                    .map_err(|_| format!("line {i}: invalid day '{day}'"))?,
+                part: part.parse().map_err(|e| format!("line {i}: {e}"))?,
+                input: input.to_string(),
+                answer: answer.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn load(path: &str) -> Result<Vec<Expected>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Can not read {path}: {e}"))?;
+    parse(&content).map_err(|e| format!("{path}: {e}"))
+}
+
+/// Looks up the expected answer in [`MANIFEST`], panicking when there is none.
+/// Meant for tests, which should fail loudly on a missing entry.
+pub fn expected(day: u8, part: Part, input: &str) -> String {
+    load(MANIFEST)
+        .unwrap()
+        .into_iter()
+        .find(|e| e.day == day && e.part == part && e.input == input)
+        .unwrap_or_else(|| panic!("No expected answer for day {day} part {part} on {input}"))
+        .answer
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manifest = "# day part input answer\n\n7 2 day07/test1.txt 5905\n";
+        let expected = Expected {
+            day: 7,
+            part: Part::Two,
+            input: "day07/test1.txt".to_string(),
+            answer: "5905".to_string(),
+        };
+        assert_eq!(Ok(vec![expected]), parse(manifest));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("7 3 day07/test1.txt 5905").is_err());
+        assert!(parse("7 2 day07/test1.txt").is_err());
+    }
+}
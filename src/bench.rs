@@ -0,0 +1,172 @@
+use crate::{input, Day, ParseError, Part};
+use std::fmt::{self, Display};
+use std::fs;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(p) => write!(f, "part{p}"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part(Part::One)),
+            "part2" => Ok(Phase::Part(Part::Two)),
+            x => Err(format!(
+                "Unknown phase '{x}', expected parse, part1 or part2"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes a non-empty list of samples.
+    pub fn of(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        Stats {
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Calls `f` `runs` times and returns the wall time of each call.
+pub fn measure<T>(runs: usize, mut f: impl FnMut() -> T) -> Vec<Duration> {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Times parsing and both parts of `day` on `input` separately.
+pub fn bench_day(day: &Day, input: &str, runs: usize) -> Result<Vec<(Phase, Stats)>, ParseError> {
+    let parsed = day.parse(input)?;
+
+    let parse = measure(runs, || day.parse(input));
+    let part1 = measure(runs, || day.part1(parsed.as_ref()));
+    let part2 = measure(runs, || day.part2(parsed.as_ref()));
+
+    Ok(vec![
+        (Phase::Parse, Stats::of(&parse)),
+        (Phase::Part(Part::One), Stats::of(&part1)),
+        (Phase::Part(Part::Two), Stats::of(&part2)),
+    ])
+}
+
+/// Median time of one phase of one day, as stored in a baseline file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Baseline {
+    pub day: u8,
+    pub phase: Phase,
+    pub median: Duration,
+}
+
+/// Parses a baseline file with one `<day> <phase> <median nanoseconds>` entry per line, see
+/// [`input::records`].
+pub fn parse_baseline(s: &str) -> Result<Vec<Baseline>, String> {
+    input::records(s, "<day> <phase> <nanoseconds>")?
+        .into_iter()
+        .map(|(i, [day, phase, nanos])| {
+            Ok(Baseline {
+                day: day
+                    .parse()
+                    .map_err(|_| format!("line {i}: invalid day '{day}'"))?,
+                phase: phase.parse().map_err(|e| format!("line {i}: {e}"))?,
+                median: Duration::from_nanos(
+                    nanos
+                        .parse()
+                        .map_err(|_| format!("line {i}: invalid duration '{nanos}'"))?,
+                ),
+            })
+        })
+        .collect()
+}
+
+pub fn format_baseline(entries: &[Baseline]) -> String {
+    let mut result = String::from("# day phase median_ns\n");
+    for e in entries {
+        result += &format!("{} {} {}\n", e.day, e.phase, e.median.as_nanos());
+    }
+    result
+}
+
+pub fn load_baseline(path: &str) -> Result<Vec<Baseline>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Can not read {path}: {e}"))?;
+    parse_baseline(&content).map_err(|e| format!("{path}: {e}"))
+}
+
+pub fn save_baseline(path: &str, entries: &[Baseline]) -> Result<(), String> {
+    fs::write(path, format_baseline(entries)).map_err(|e| format!("Can not write {path}: {e}"))
+}
+
+/// Relative change of `current` against `baseline`, in percent.
+pub fn change(baseline: Duration, current: Duration) -> f64 {
+    let base = baseline.as_secs_f64();
+    if base == 0.0 {
+        return 0.0;
+    }
+    (current.as_secs_f64() - base) / base * 100.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 3, 2, 4].map(Duration::from_millis);
+        let stats = Stats::of(&samples);
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(3), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.max);
+    }
+
+    #[test]
+    fn test_baseline_roundtrip() {
+        let entries = vec![
+            Baseline {
+                day: 5,
+                phase: Phase::Parse,
+                median: Duration::from_nanos(1234),
+            },
+            Baseline {
+                day: 7,
+                phase: Phase::Part(Part::Two),
+                median: Duration::from_nanos(98765),
+            },
+        ];
+        assert_eq!(
+            Ok(entries.clone()),
+            parse_baseline(&format_baseline(&entries))
+        );
+    }
+}
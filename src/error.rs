@@ -0,0 +1,184 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Malformed puzzle input: what was found where, and what should have been there instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input.
+    pub line: usize,
+    /// 1-based column within `line`.
+    pub column: usize,
+    /// The offending text, empty at the end of a line or of the input.
+    pub text: String,
+    pub expected: String,
+    /// Extra advice on how to fix the input, shown below the offending line.
+    pub hint: Option<String>,
+}
+
+impl ParseError {
+    /// Error in a single line of input, `offset` bytes into it. The line number is filled in by
+    /// whoever splits the input into lines, see [`ParseError::on_line`].
+    pub fn new(offset: usize, text: &str, expected: &str) -> Self {
+        ParseError {
+            line: 1,
+            column: offset + 1,
+            text: text.to_string(),
+            expected: expected.to_string(),
+            hint: None,
+        }
+    }
+
+    pub fn with_hint(mut self, hint: &str) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    /// Places an error of a parser that only saw a single line on the 1-based `line`.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Moves an error of a parser that only saw a substring to the substring's `offset`.
+    pub fn shift(mut self, offset: usize) -> Self {
+        self.column += offset;
+        self
+    }
+
+    fn message(&self) -> String {
+        if self.text.is_empty() {
+            format!("expected {}, found end of line", self.expected)
+        } else {
+            format!("expected {}, found '{}'", self.expected, self.text)
+        }
+    }
+
+    /// Compiler-style diagnostic quoting the offending line of `source`, read from `path`, with
+    /// the bad span underlined.
+    pub fn render(&self, source: &str, path: &str) -> String {
+        let mut result = format!("{}\n", self.message());
+
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        result += &format!("{gutter}--> {path}:{}:{}\n", self.line, self.column);
+
+        if let Some(line) = source.lines().nth(self.line - 1) {
+            let start = (self.column - 1).min(line.len());
+            let indent: String = line
+                .get(..start)
+                .unwrap_or_default()
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let carets = "^".repeat(self.text.chars().count().max(1));
+
+            result += &format!("{gutter} |\n");
+            result += &format!("{number} | {line}\n");
+            result += &format!("{gutter} | {indent}{carets}\n");
+        }
+
+        if let Some(hint) = &self.hint {
+            result += &format!("{gutter} = hint: {hint}\n");
+        }
+
+        result
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line,
+            self.column,
+            self.message()
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `text`, found `offset` bytes into its line, as a number.
+pub fn number<T: FromStr>(text: &str, offset: usize) -> Result<T, ParseError> {
+    text.parse().map_err(|_| {
+        let e = ParseError::new(offset, text, "a number");
+
+        if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) {
+            let kind = std::any::type_name::<T>();
+            e.with_hint(&format!("{text} is too large to fit into a {kind}"))
+        } else {
+            e
+        }
+    })
+}
+
+/// Words of `s` separated by spaces or tabs, together with their byte offset.
+pub fn words(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split([' ', '\t'])
+        .scan(0, |offset, w| {
+            let start = *offset;
+            *offset += w.len() + 1;
+            Some((start, w))
+        })
+        .filter(|(_, w)| !w.is_empty())
+}
+
+/// Splits `s` at every `separator`, keeping the byte offset of each part.
+pub fn split<'a>(s: &'a str, separator: &'a str) -> impl Iterator<Item = (usize, &'a str)> {
+    s.split(separator).scan(0, move |offset, w| {
+        let start = *offset;
+        *offset += w.len() + separator.len();
+        Some((start, w))
+    })
+}
+
+/// Strips `prefix` from the start of `s`, or reports what was found instead.
+pub fn expect<'a>(s: &'a str, prefix: &str, offset: usize) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix).ok_or_else(|| {
+        let found = s.split(' ').next().unwrap_or_default();
+        ParseError::new(offset, found, &format!("'{}'", prefix.trim()))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        let words: Vec<_> = words(" 41 48 \t6 | 83").collect();
+        assert_eq!(
+            vec![(1, "41"), (4, "48"), (8, "6"), (10, "|"), (12, "83")],
+            words
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let e = ParseError::new(4, "x1", "a number").shift(6).on_line(3);
+        assert_eq!(
+            "line 3, column 11: expected a number, found 'x1'",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "Card 1: 41 | 83\nCard 2: 13 x2 | 61\n";
+        let e = ParseError::new(11, "x2", "a number")
+            .on_line(2)
+            .with_hint("numbers are separated by spaces");
+
+        let expected = "\
+expected a number, found 'x2'
+ --> test.txt:2:12
+  |
+2 | Card 2: 13 x2 | 61
+  |            ^^
+  = hint: numbers are separated by spaces
+";
+        assert_eq!(expected, e.render(source, "test.txt"));
+    }
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::io::{self, Read};
+
+/// Brings puzzle input into the shape every day expects: `\n` line endings, no byte order
+/// mark, no trailing whitespace on a line and no blank lines at the end. Non-empty input always
+/// ends in a single newline.
+pub fn normalize(s: &str) -> String {
+    let s = s.strip_prefix('\u{feff}').unwrap_or(s);
+
+    let mut lines: Vec<&str> = s
+        .split('\n')
+        .map(|l| l.trim_end_matches([' ', '\t', '\r']))
+        .collect();
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    if lines.is_empty() {
+        return String::new();
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Splits a table of whitespace separated fields into records of exactly `N` fields, each with
+/// its line number. Blank lines and lines starting with `#` are ignored. `format` describes a
+/// record in errors, e.g. `<day> <part>`.
+pub fn records<'a, const N: usize>(
+    s: &'a str,
+    format: &str,
+) -> Result<Vec<(usize, [&'a str; N])>, String> {
+    let mut result = vec![];
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let fields: [&str; N] = fields
+            .try_into()
+            .map_err(|_| format!("line {}: expected '{format}', found '{line}'", i + 1))?;
+
+        result.push((i + 1, fields));
+    }
+
+    Ok(result)
+}
+
+/// Reads and normalizes the input at `path`, `-` being stdin.
+pub fn load(path: &str) -> Result<String, String> {
+    let content = if path == "-" {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Can not read stdin: {e}"))?;
+        content
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("Can not read {path}: {e}"))?
+    };
+
+    Ok(normalize(&content))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_records() {
+        let table = "# a b\n\n1 2\n  3\t4  \n";
+        assert_eq!(
+            Ok(vec![(3, ["1", "2"]), (4, ["3", "4"])]),
+            records(table, "<a> <b>")
+        );
+        assert_eq!(
+            Err("line 2: expected '<a> <b>', found '1 2 3'".to_string()),
+            records::<2>("1 2\n1 2 3\n", "<a> <b>")
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!("a\nb\n", normalize("\u{feff}a\r\nb \r\n\r\n\n"));
+        assert_eq!("a\n\nb\n", normalize("a\t\n\nb"));
+        assert_eq!("", normalize("\n\n"));
+    }
+
+    fn variants(s: &str) -> Vec<String> {
+        vec![
+            s.replace('\n', "\r\n"),
+            format!("\u{feff}{s}"),
+            format!("\u{feff}{}\r\n\r\n", s.replace('\n', "\r\n")),
+            format!("{s}\n\n  \n"),
+            s.trim_end().to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_examples() {
+        for day in crate::days() {
+            let path = format!("./{}/test1.txt", day.dir());
+            let clean = fs::read_to_string(&path).unwrap();
+            let expected = day.solve(&normalize(&clean)).unwrap();
+
+            for variant in variants(&clean) {
+                assert_eq!(
+                    expected,
+                    day.solve(&normalize(&variant)).unwrap(),
+                    "{path}: {variant:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::Part;
+use std::fmt::{self, Display, Write};
+use std::time::Duration;
+
+/// Minimal JSON value, enough to report results to scripts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<const N: usize>(fields: [(&str, Json); N]) -> Json {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    /// A number of any width, as a string if it does not fit into an `Int`.
+    pub fn number(n: impl Display) -> Json {
+        let s = n.to_string();
+        s.parse().map_or(Json::String(s), Json::Int)
+    }
+
+    /// Renders a list of values as an array with one value per line.
+    pub fn lines(values: &[Json]) -> String {
+        if values.is_empty() {
+            return "[]".to_string();
+        }
+
+        let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        format!("[\n  {}\n]", values.join(",\n  "))
+    }
+}
+
+fn escape(s: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Int(i) => write!(f, "{i}"),
+            Json::Float(x) if x.is_finite() => write!(f, "{x}"),
+            Json::Float(_) => write!(f, "null"),
+            Json::String(s) => escape(s, f),
+            Json::Array(values) => {
+                f.write_char('[')?;
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    escape(k, f)?;
+                    write!(f, ":{v}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Json::Bool(b)
+    }
+}
+
+impl From<u8> for Json {
+    fn from(i: u8) -> Self {
+        Json::Int(i.into())
+    }
+}
+
+impl From<usize> for Json {
+    fn from(i: usize) -> Self {
+        Json::Int(i as i128)
+    }
+}
+
+impl From<Part> for Json {
+    fn from(p: Part) -> Self {
+        match p {
+            Part::One => Json::Int(1),
+            Part::Two => Json::Int(2),
+        }
+    }
+}
+
+impl From<f64> for Json {
+    fn from(x: f64) -> Self {
+        Json::Float(x)
+    }
+}
+
+/// Durations are reported in nanoseconds.
+impl From<Duration> for Json {
+    fn from(d: Duration) -> Self {
+        Json::Int(d.as_nanos() as i128)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(o: Option<T>) -> Self {
+        o.map_or(Json::Null, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let value = Json::object([
+            ("day", 7u8.into()),
+            ("answer", "6440".into()),
+            ("error", Json::Null),
+            ("ok", true.into()),
+            ("parts", Json::Array(vec![1u8.into(), 2u8.into()])),
+        ]);
+        assert_eq!(
+            r#"{"day":7,"answer":"6440","error":null,"ok":true,"parts":[1,2]}"#,
+            value.to_string()
+        );
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(Json::Int(-42), Json::number(-42));
+        let big = "1".repeat(40);
+        assert_eq!(Json::String(big.clone()), Json::number(&big));
+    }
+
+    #[test]
+    fn test_escape() {
+        let value = Json::from("say \"hi\"\n\tto C:\\ \u{1}");
+        assert_eq!(r#""say \"hi\"\n\tto C:\\ \u0001""#, value.to_string());
+    }
+}
@@ -0,0 +1,166 @@
+// Standard output belongs to the runner, `--format json` has to stay parseable. Progress and
+// debug output from days goes to stderr.
+#![deny(clippy::print_stdout)]
+
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+pub use error::ParseError;
+pub use report::Report;
+
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod input;
+pub mod json;
+pub mod report;
+pub mod scaffold;
+
+#[path = "../day01/mod.rs"]
+pub mod day01;
+#[path = "../day02/mod.rs"]
+pub mod day02;
+#[path = "../day03/mod.rs"]
+pub mod day03;
+#[path = "../day04/mod.rs"]
+pub mod day04;
+#[path = "../day05/mod.rs"]
+pub mod day05;
+#[path = "../day06/mod.rs"]
+pub mod day06;
+#[path = "../day07/mod.rs"]
+pub mod day07;
+
+/// A single day of the calendar: parse the puzzle input once, then answer both parts from it.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Problems of an input that parses, but likely is not what was meant, like overlapping
+    /// mappings on day 5.
+    fn validate(_input: &Self::Input) -> Vec<ParseError> {
+        vec![]
+    }
+
+    /// Details behind the answers, for days where there is more to show than a number.
+    fn report(_input: &Self::Input) -> Option<Report> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            x => Err(format!("Unknown part '{x}', expected 1 or 2")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Type-erased handle to a [`Solution`], so days can be picked at runtime by number.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    parse: fn(&str) -> Result<Box<dyn Any>, ParseError>,
+    part1: fn(&dyn Any) -> String,
+    part2: fn(&dyn Any) -> String,
+    validate: fn(&dyn Any) -> Vec<ParseError>,
+    report: fn(&dyn Any) -> Option<Report>,
+}
+
+impl Day {
+    pub fn of<S: Solution + 'static>() -> Day {
+        Day {
+            number: S::DAY,
+            parse: |s| Ok(Box::new(S::parse(s)?)),
+            part1: |i| S::part1(i.downcast_ref().unwrap()).to_string(),
+            part2: |i| S::part2(i.downcast_ref().unwrap()).to_string(),
+            validate: |i| S::validate(i.downcast_ref().unwrap()),
+            report: |i| S::report(i.downcast_ref().unwrap()),
+        }
+    }
+
+    /// Parses input that has been through [`input::normalize`], like [`input::load`] returns.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        (self.parse)(input)
+    }
+
+    pub fn part1(&self, parsed: &dyn Any) -> String {
+        (self.part1)(parsed)
+    }
+
+    pub fn part2(&self, parsed: &dyn Any) -> String {
+        (self.part2)(parsed)
+    }
+
+    pub fn validate(&self, parsed: &dyn Any) -> Vec<ParseError> {
+        (self.validate)(parsed)
+    }
+
+    pub fn report(&self, parsed: &dyn Any) -> Option<Report> {
+        (self.report)(parsed)
+    }
+
+    pub fn answer(&self, parsed: &dyn Any, part: Part) -> String {
+        match part {
+            Part::One => self.part1(parsed),
+            Part::Two => self.part2(parsed),
+        }
+    }
+
+    /// Directory holding this day's source and inputs, e.g. `day05`.
+    pub fn dir(&self) -> String {
+        format!("day{:02}", self.number)
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("./{}/input.txt", self.dir())
+    }
+
+    pub fn solve(&self, input: &str) -> Result<(String, String), ParseError> {
+        let parsed = self.parse(input)?;
+        Ok((self.part1(parsed.as_ref()), self.part2(parsed.as_ref())))
+    }
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::of::<day01::Day01>(),
+        Day::of::<day02::Day02>(),
+        Day::of::<day03::Day03>(),
+        Day::of::<day04::Day04>(),
+        Day::of::<day05::Day05>(),
+        Day::of::<day06::Day06>(),
+        Day::of::<day07::Day07>(),
+    ]
+}
+
+pub fn day(number: u8) -> Option<Day> {
+    days().into_iter().find(|d| d.number == number)
+}
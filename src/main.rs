@@ -0,0 +1,719 @@
+use aoc::answers::{self, Expected};
+use aoc::bench::{self, Baseline};
+use aoc::input;
+use aoc::json::Json;
+use aoc::{Day, Part};
+use std::any::Any;
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: aoc run <day|all> [--part <1|2>] [--input <path|->] [--strict] [--format <text|json>]
+       aoc verify [day|all] [--manifest <path>] [--format <text|json>]
+       aoc report <day> [--input <path|->] [--format <text|json>]
+       aoc new <day>
+       aoc bench [day|all] [--runs <n>] [--save <path>] [--baseline <path>] [--threshold <percent>]
+                 [--format <text|json>]
+
+Options:
+  --part <1|2>        Only run the given part
+  --input <path>      Read the puzzle input from <path> instead of ./dayNN/input.txt,
+                      use '-' to read from stdin
+  --strict            Refuse inputs with warnings, like overlapping maps on day 5
+  --manifest <path>   Expected answers to verify against [default: ./answers.txt]
+  --runs <n>          How often each phase is timed [default: 10]
+  --save <path>       Write the measured medians to a baseline file
+  --baseline <path>   Compare the measured medians against a saved baseline
+  --threshold <pct>   Slowdown against the baseline counted as a regression [default: 10]
+  --format <fmt>      Print a table for humans or a JSON array for scripts [default: text]";
+
+enum Selection {
+    All,
+    Single(Day),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            x => Err(format!("Unknown format '{x}', expected text or json")),
+        }
+    }
+}
+
+struct Run {
+    days: Selection,
+    part: Option<Part>,
+    input: Option<String>,
+    strict: bool,
+    format: Format,
+}
+
+/// Answer to one part of one day, or why there is none.
+struct Outcome {
+    day: u8,
+    part: Part,
+    input: String,
+    answer: Result<String, String>,
+    /// Rendered [`Day::validate`] findings on the input.
+    warnings: Vec<String>,
+    parse: Duration,
+    solve: Duration,
+}
+
+impl Outcome {
+    fn json(&self, status: &str) -> Vec<(String, Json)> {
+        let (answer, error) = match &self.answer {
+            Ok(a) => (Some(a.clone()), None),
+            Err(e) => (None, Some(e.clone())),
+        };
+
+        vec![
+            ("day".to_string(), self.day.into()),
+            ("part".to_string(), self.part.into()),
+            ("input".to_string(), self.input.as_str().into()),
+            ("status".to_string(), status.into()),
+            ("answer".to_string(), answer.into()),
+            ("error".to_string(), error.into()),
+            (
+                "warnings".to_string(),
+                Json::Array(self.warnings.iter().map(|w| w.as_str().into()).collect()),
+            ),
+            ("parse_ns".to_string(), self.parse.into()),
+            ("solve_ns".to_string(), self.solve.into()),
+        ]
+    }
+}
+
+fn parse_day(s: &str) -> Result<Selection, String> {
+    if s == "all" {
+        return Ok(Selection::All);
+    }
+
+    let number: u8 = s
+        .parse()
+        .map_err(|_| format!("Invalid day '{s}', expected a number or 'all'"))?;
+
+    match aoc::day(number) {
+        Some(day) => Ok(Selection::Single(day)),
+        None => Err(format!("Day {number} is not implemented")),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Run, String> {
+    let mut args = args.iter();
+
+    let days = match args.next() {
+        Some(s) => parse_day(s)?,
+        None => return Err("Missing day".to_string()),
+    };
+
+    let mut part = None;
+    let mut input = None;
+    let mut strict = false;
+    let mut format = Format::Text;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for '{arg}'"))
+        };
+
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(value()?.parse()?),
+            "--input" | "-i" => input = Some(value()?.clone()),
+            "--strict" => strict = true,
+            "--format" | "-f" => format = value()?.parse()?,
+            x => return Err(format!("Unknown argument '{x}'")),
+        }
+    }
+
+    if matches!(days, Selection::All) && input.is_some() {
+        return Err("'--input' can only be used with a single day".to_string());
+    }
+
+    Ok(Run {
+        days,
+        part,
+        input,
+        strict,
+        format,
+    })
+}
+
+fn source_name(path: &str) -> &str {
+    if path == "-" {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs `f`, turning a panic into an error instead of aborting the whole command.
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+
+    result.map_err(|e| format!("panicked: {}", panic_message(e)))
+}
+
+/// Answers `parts` of `day` on the input at `path`. With `strict`, inputs that `Day::validate`
+/// finds problems with are refused instead of answered with warnings.
+fn solve(day: &Day, parts: &[Part], path: &str, strict: bool) -> Vec<Outcome> {
+    let outcome = |part: Part, answer: Result<String, String>, parse, solve| Outcome {
+        day: day.number,
+        part,
+        input: path.to_string(),
+        answer,
+        warnings: vec![],
+        parse,
+        solve,
+    };
+
+    let content = match input::load(path) {
+        Ok(c) => c,
+        Err(e) => {
+            return parts
+                .iter()
+                .map(|p| outcome(*p, Err(e.clone()), Duration::ZERO, Duration::ZERO))
+                .collect()
+        }
+    };
+
+    let start = Instant::now();
+    let parsed = catch(|| day.parse(&content))
+        .and_then(|r| r.map_err(|e| e.render(&content, source_name(path))));
+    let parse = start.elapsed();
+
+    let parsed = match parsed {
+        Ok(p) => p,
+        Err(e) => {
+            return parts
+                .iter()
+                .map(|p| outcome(*p, Err(e.clone()), parse, Duration::ZERO))
+                .collect()
+        }
+    };
+
+    let warnings: Vec<String> = day
+        .validate(parsed.as_ref())
+        .iter()
+        .map(|w| w.render(&content, source_name(path)))
+        .collect();
+
+    if strict && !warnings.is_empty() {
+        let e = format!(
+            "refusing input with warnings (--strict)\n{}",
+            warnings.concat()
+        );
+        return parts
+            .iter()
+            .map(|p| outcome(*p, Err(e.clone()), parse, Duration::ZERO))
+            .collect();
+    }
+
+    parts
+        .iter()
+        .map(|p| {
+            let start = Instant::now();
+            let answer = catch(|| day.answer(parsed.as_ref(), *p));
+            Outcome {
+                warnings: warnings.clone(),
+                ..outcome(*p, answer, parse, start.elapsed())
+            }
+        })
+        .collect()
+}
+
+fn run(args: &[String]) -> Result<bool, String> {
+    let run = parse_run(args)?;
+
+    let parts = match run.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    let days = match run.days {
+        Selection::All => aoc::days(),
+        Selection::Single(day) => vec![day],
+    };
+
+    let mut outcomes = vec![];
+    for day in &days {
+        let path = run.input.clone().unwrap_or_else(|| day.input_path());
+        outcomes.extend(solve(day, &parts, &path, run.strict));
+    }
+
+    match run.format {
+        Format::Json => {
+            let values: Vec<Json> = outcomes
+                .iter()
+                .map(|o| {
+                    let status = if o.answer.is_ok() { "ok" } else { "error" };
+                    Json::Object(o.json(status))
+                })
+                .collect();
+            println!("{}", Json::lines(&values));
+        }
+        Format::Text => {
+            let mut last_error = None;
+            for o in &outcomes {
+                // Warnings are about the input, which all parts of a day share.
+                if o.part == parts[0] {
+                    for w in &o.warnings {
+                        eprintln!("warning: day {}: {}", o.day, w.trim_end());
+                    }
+                }
+
+                match &o.answer {
+                    Ok(answer) => println!("Day {} part {}: {answer}", o.day, o.part),
+                    // A broken input fails both parts the same way, only report it once.
+                    Err(e) if last_error == Some(e) => {}
+                    Err(e) => {
+                        eprintln!("error: day {}: {}", o.day, e.trim_end());
+                        last_error = Some(e);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(outcomes.iter().all(|o| o.answer.is_ok()))
+}
+
+fn check(entry: &Expected) -> Outcome {
+    match aoc::day(entry.day) {
+        Some(day) => solve(&day, &[entry.part], &entry.input, false).remove(0),
+        None => Outcome {
+            day: entry.day,
+            part: entry.part,
+            input: entry.input.clone(),
+            answer: Err(format!("day {} is not implemented", entry.day)),
+            warnings: vec![],
+            parse: Duration::ZERO,
+            solve: Duration::ZERO,
+        },
+    }
+}
+
+fn print_table<R: AsRef<[String]>>(rows: &[R]) {
+    let columns = rows.first().map_or(0, |r| r.as_ref().len());
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().map(|r| r.as_ref()[i].len()).max().unwrap())
+        .collect();
+
+    for row in rows {
+        let cells: Vec<String> = row
+            .as_ref()
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c:<w$}"))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn verify(args: &[String]) -> Result<bool, String> {
+    let mut days = Selection::All;
+    let mut manifest = answers::MANIFEST.to_string();
+    let mut format = Format::Text;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for '{arg}'"))
+        };
+
+        match arg.as_str() {
+            "--manifest" | "-m" => manifest = value()?.clone(),
+            "--format" | "-f" => format = value()?.parse()?,
+            x => days = parse_day(x)?,
+        }
+    }
+
+    let entries: Vec<Expected> = answers::load(&manifest)?
+        .into_iter()
+        .filter(|e| match &days {
+            Selection::All => true,
+            Selection::Single(day) => day.number == e.day,
+        })
+        .collect();
+
+    let outcomes: Vec<Outcome> = entries.iter().map(check).collect();
+
+    if format == Format::Json {
+        let values: Vec<Json> = entries
+            .iter()
+            .zip(&outcomes)
+            .map(|(entry, outcome)| {
+                let status = match &outcome.answer {
+                    Ok(a) if *a == entry.answer => "pass",
+                    Ok(_) => "fail",
+                    Err(_) => "error",
+                };
+
+                let mut fields = outcome.json(status);
+                fields.push(("expected".to_string(), entry.answer.as_str().into()));
+                Json::Object(fields)
+            })
+            .collect();
+        println!("{}", Json::lines(&values));
+
+        return Ok(entries
+            .iter()
+            .zip(&outcomes)
+            .all(|(e, o)| o.answer.as_ref() == Ok(&e.answer)));
+    }
+
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Part".to_string(),
+        "Input".to_string(),
+        "Expected".to_string(),
+        "Actual".to_string(),
+        "Status".to_string(),
+    ]];
+    let mut failures = vec![];
+
+    for (entry, outcome) in entries.iter().zip(&outcomes) {
+        let result = &outcome.answer;
+        let (actual, status) = match result {
+            Ok(a) if *a == entry.answer => (a.clone(), "ok"),
+            Ok(a) => (a.clone(), "FAIL"),
+            Err(_) => ("-".to_string(), "ERROR"),
+        };
+
+        if status != "ok" {
+            failures.push((entry, result));
+        }
+
+        rows.push([
+            entry.day.to_string(),
+            entry.part.to_string(),
+            entry.input.clone(),
+            entry.answer.clone(),
+            actual,
+            status.to_string(),
+        ]);
+    }
+
+    print_table(&rows);
+
+    for (entry, result) in &failures {
+        println!(
+            "\nday {} part {} on {}:",
+            entry.day, entry.part, entry.input
+        );
+        match result {
+            Ok(actual) => {
+                println!("  - expected: {}", entry.answer);
+                println!("  + actual:   {actual}");
+            }
+            Err(e) => {
+                for line in e.trim_end().lines() {
+                    println!("  {line}");
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed",
+        entries.len() - failures.len(),
+        failures.len()
+    );
+
+    Ok(failures.is_empty())
+}
+
+fn bench(args: &[String]) -> Result<bool, String> {
+    let mut days = Selection::All;
+    let mut runs: usize = 10;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold: f64 = 10.0;
+    let mut format = Format::Text;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for '{arg}'"))
+        };
+
+        match arg.as_str() {
+            "--runs" | "-n" => {
+                let v = value()?;
+                runs = v
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("Invalid number of runs '{v}'"))?;
+            }
+            "--save" => save = Some(value()?.clone()),
+            "--baseline" | "-b" => baseline = Some(bench::load_baseline(value()?)?),
+            "--threshold" | "-t" => {
+                let v = value()?;
+                threshold = v
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| format!("Invalid threshold '{v}'"))?;
+            }
+            "--format" | "-f" => format = value()?.parse()?,
+            x => days = parse_day(x)?,
+        }
+    }
+
+    let days = match days {
+        Selection::All => aoc::days(),
+        Selection::Single(day) => vec![day],
+    };
+
+    let mut rows = vec![[
+        "Day", "Phase", "Min", "Median", "Max", "Baseline", "Change", "Status",
+    ]
+    .map(String::from)];
+    let mut values = vec![];
+    let mut measured = vec![];
+    let mut regressions = 0;
+    let mut errors = vec![];
+
+    for day in &days {
+        let path = day.input_path();
+        let timings = input::load(&path).and_then(|content| {
+            catch(|| bench::bench_day(day, &content, runs))
+                .and_then(|r| r.map_err(|e| e.render(&content, &path)))
+        });
+
+        let timings = match timings {
+            Ok(t) => t,
+            Err(e) => {
+                values.push(Json::object([
+                    ("day", day.number.into()),
+                    ("input", path.as_str().into()),
+                    ("runs", runs.into()),
+                    ("status", "error".into()),
+                    ("error", e.as_str().into()),
+                ]));
+
+                let mut row = ["-"; 8].map(String::from);
+                row[0] = day.number.to_string();
+                row[7] = "ERROR".to_string();
+                rows.push(row);
+
+                errors.push((day.number, e));
+                continue;
+            }
+        };
+
+        for (phase, stats) in timings {
+            let previous = baseline.as_ref().and_then(|b: &Vec<Baseline>| {
+                b.iter().find(|e| e.day == day.number && e.phase == phase)
+            });
+
+            let change = previous.map(|p| bench::change(p.median, stats.median));
+            let regression = change.map(|c| c > threshold);
+            if regression == Some(true) {
+                regressions += 1;
+            }
+
+            values.push(Json::object([
+                ("day", day.number.into()),
+                ("phase", phase.to_string().into()),
+                ("input", path.as_str().into()),
+                ("runs", runs.into()),
+                ("min_ns", stats.min.into()),
+                ("median_ns", stats.median.into()),
+                ("max_ns", stats.max.into()),
+                ("baseline_ns", previous.map(|p| p.median).into()),
+                ("change_percent", change.into()),
+                (
+                    "status",
+                    regression
+                        .map(|r| if r { "regression" } else { "ok" })
+                        .into(),
+                ),
+            ]));
+
+            let (base, change, status) = match (previous, change, regression) {
+                (Some(p), Some(change), Some(regression)) => (
+                    format!("{:.3?}", p.median),
+                    format!("{change:+.1}%"),
+                    if regression { "REGRESSION" } else { "ok" },
+                ),
+                _ => ("-".to_string(), "-".to_string(), ""),
+            };
+
+            rows.push([
+                day.number.to_string(),
+                phase.to_string(),
+                format!("{:.3?}", stats.min),
+                format!("{:.3?}", stats.median),
+                format!("{:.3?}", stats.max),
+                base,
+                change,
+                status.to_string(),
+            ]);
+
+            measured.push(Baseline {
+                day: day.number,
+                phase,
+                median: stats.median,
+            });
+        }
+    }
+
+    if let Some(path) = &save {
+        bench::save_baseline(path, &measured)?;
+    }
+
+    if format == Format::Json {
+        println!("{}", Json::lines(&values));
+        return Ok(regressions == 0 && errors.is_empty());
+    }
+
+    print_table(&rows);
+
+    for (day, e) in &errors {
+        eprintln!("error: day {day}: {}", e.trim_end());
+    }
+
+    if let Some(path) = save {
+        println!("\nSaved baseline to {path}");
+    }
+
+    if baseline.is_some() {
+        println!("\n{regressions} regression(s) beyond {threshold}%");
+    }
+
+    Ok(regressions == 0 && errors.is_empty())
+}
+
+fn report(args: &[String]) -> Result<(), String> {
+    let mut days = None;
+    let mut input = None;
+    let mut format = Format::Text;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for '{arg}'"))
+        };
+
+        match arg.as_str() {
+            "--input" | "-i" => input = Some(value()?.clone()),
+            "--format" | "-f" => format = value()?.parse()?,
+            x => days = Some(parse_day(x)?),
+        }
+    }
+
+    let day = match days {
+        Some(Selection::Single(day)) => day,
+        Some(Selection::All) => return Err("'report' works on a single day".to_string()),
+        None => return Err("Missing day".to_string()),
+    };
+
+    let path = input.unwrap_or_else(|| day.input_path());
+    let content = input::load(&path)?;
+    let parsed = day.parse(&content).map_err(|e| {
+        format!(
+            "day {}: {}",
+            day.number,
+            e.render(&content, source_name(&path))
+        )
+    })?;
+    let report = day
+        .report(parsed.as_ref())
+        .ok_or_else(|| format!("Day {} has no report", day.number))?;
+
+    match format {
+        Format::Json => println!("{}", Json::lines(&report.json())),
+        Format::Text => print_table(&report.cells()),
+    }
+
+    Ok(())
+}
+
+fn new(args: &[String]) -> Result<(), String> {
+    let [day] = args else {
+        return Err("Expected exactly one day".to_string());
+    };
+
+    let day: u8 = day
+        .parse()
+        .map_err(|_| format!("Invalid day '{day}', expected a number"))?;
+
+    for path in aoc::scaffold::create(Path::new("."), day)? {
+        println!("Wrote {path}");
+    }
+
+    println!("\nPaste the example into day{day:02}/test1.txt, fill in its answers in answers.txt");
+    println!("and rebuild to get `aoc run {day}`.");
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => match run(&args[1..]) {
+            Ok(true) => Ok(()),
+            Ok(false) => return ExitCode::FAILURE,
+            Err(e) => Err(e),
+        },
+        Some("verify") => match verify(&args[1..]) {
+            Ok(true) => Ok(()),
+            Ok(false) => return ExitCode::FAILURE,
+            Err(e) => Err(e),
+        },
+        Some("report") => report(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("bench") => match bench(&args[1..]) {
+            Ok(true) => Ok(()),
+            Ok(false) => return ExitCode::FAILURE,
+            Err(e) => Err(e),
+        },
+        Some("help" | "--help" | "-h") => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some(x) => Err(format!("Unknown command '{x}'")),
+        None => Err(format!("Missing command\n\n{USAGE}")),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e.trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::json::Json;
+
+/// Details behind a day's answers, one row per item of the puzzle, e.g. per race.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Json>>,
+}
+
+impl Report {
+    pub fn new(columns: &[&str]) -> Self {
+        Report {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<Json>) {
+        assert_eq!(
+            self.columns.len(),
+            row.len(),
+            "row does not match the columns"
+        );
+        self.rows.push(row);
+    }
+
+    /// One object per row, keyed by column.
+    pub fn json(&self) -> Vec<Json> {
+        self.rows
+            .iter()
+            .map(|row| Json::Object(self.columns.iter().cloned().zip(row.clone()).collect()))
+            .collect()
+    }
+
+    /// The header followed by every row as text, strings unquoted and missing values as `-`.
+    pub fn cells(&self) -> Vec<Vec<String>> {
+        let header = self.columns.clone();
+        let rows = self.rows.iter().map(|row| {
+            row.iter()
+                .map(|v| match v {
+                    Json::Null => "-".to_string(),
+                    Json::String(s) => s.clone(),
+                    v => v.to_string(),
+                })
+                .collect()
+        });
+
+        std::iter::once(header).chain(rows).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let mut report = Report::new(&["race", "wins", "note"]);
+        report.push(vec![1u8.into(), 4u8.into(), Json::Null]);
+        report.push(vec![2u8.into(), 8u8.into(), "close".into()]);
+
+        assert_eq!(
+            vec![
+                vec!["race", "wins", "note"],
+                vec!["1", "4", "-"],
+                vec!["2", "8", "close"],
+            ],
+            report.cells()
+        );
+        assert_eq!(
+            r#"{"race":2,"wins":8,"note":"close"}"#,
+            report.json()[1].to_string()
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use regex_lite::Regex;
+use std::fs;
+use std::path::Path;
+
+const TEMPLATE: &str = include_str!("../template/mod.rs");
+
+/// Source of a new day module, made from `template/mod.rs`.
+pub fn render(day: u8) -> String {
+    TEMPLATE
+        .replace("const DAY: u8 = 0;", &format!("const DAY: u8 = {day};"))
+        .replace("Day00", &format!("Day{day:02}"))
+        .replace("day00", &format!("day{day:02}"))
+}
+
+/// Inserts `new` before the first entry whose day number (captured by `re` on its first line)
+/// is larger than `day`, or after the last entry. Every entry spans `new.len()` lines.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    re: &Regex,
+    day: u8,
+    new: &[String],
+) -> Result<(), String> {
+    let numbered: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| re.captures(l).map(|c| (i, c[1].parse().unwrap())))
+        .collect();
+
+    if numbered.iter().any(|(_, n)| *n == day) {
+        return Err(format!("Day {day} is already registered"));
+    }
+
+    let at = match numbered.iter().find(|(_, n)| *n > day) {
+        Some((i, _)) => *i,
+        None => match numbered.last() {
+            Some((i, _)) => *i + new.len(),
+            None => return Err(format!("No registered days found matching '{re}'")),
+        },
+    };
+
+    for (i, l) in new.iter().enumerate() {
+        lines.insert(at + i, l.clone());
+    }
+
+    Ok(())
+}
+
+/// Adds the module declaration and the [`crate::days`] entry for `day` to the source of `lib.rs`.
+pub fn register(lib: &str, day: u8) -> Result<String, String> {
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    let module = Regex::new(r#"^#\[path = "\.\./day(\d\d)/mod\.rs"\]$"#).unwrap();
+    insert_sorted(
+        &mut lines,
+        &module,
+        day,
+        &[
+            format!("#[path = \"../day{day:02}/mod.rs\"]"),
+            format!("pub mod day{day:02};"),
+        ],
+    )?;
+
+    let entry = Regex::new(r"^\s*Day::of::<day(\d\d)::Day\d\d>\(\),$").unwrap();
+    insert_sorted(
+        &mut lines,
+        &entry,
+        day,
+        &[format!("        Day::of::<day{day:02}::Day{day:02}>(),")],
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates `dayNN/` under `root` from the template, registers it in `src/lib.rs` and adds
+/// placeholder entries to the answers manifest. Returns the paths that were written.
+pub fn create(root: &Path, day: u8) -> Result<Vec<String>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day {day}, expected 1 to 25"));
+    }
+
+    let dir = root.join(format!("day{day:02}"));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let lib = fs::read_to_string(&lib_path)
+        .map_err(|e| format!("Can not read {}: {e}", lib_path.display()))?;
+    let lib = register(&lib, day)?;
+
+    let manifest_path = root.join("answers.txt");
+    let mut manifest = fs::read_to_string(&manifest_path).unwrap_or_default();
+    if !manifest.is_empty() && !manifest.ends_with('\n') {
+        manifest.push('\n');
+    }
+    manifest +=
+        &format!("\n# {day} 1 day{day:02}/test1.txt ?\n# {day} 2 day{day:02}/test1.txt ?\n");
+
+    let files = [
+        (dir.join("mod.rs"), render(day)),
+        (dir.join("input.txt"), String::new()),
+        (dir.join("test1.txt"), String::new()),
+        (lib_path, lib),
+        (manifest_path, manifest),
+    ];
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Can not create {}: {e}", dir.display()))?;
+
+    let mut written = vec![];
+    for (path, content) in files {
+        fs::write(&path, content).map_err(|e| format!("Can not write {}: {e}", path.display()))?;
+        written.push(path.display().to_string());
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LIB: &str = r#"#[path = "../day01/mod.rs"]
+pub mod day01;
+#[path = "../day03/mod.rs"]
+pub mod day03;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::of::<day01::Day01>(),
+        Day::of::<day03::Day03>(),
+    ]
+}
+"#;
+
+    #[test]
+    fn test_register() {
+        let expected = r#"#[path = "../day01/mod.rs"]
+pub mod day01;
+#[path = "../day02/mod.rs"]
+pub mod day02;
+#[path = "../day03/mod.rs"]
+pub mod day03;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::of::<day01::Day01>(),
+        Day::of::<day02::Day02>(),
+        Day::of::<day03::Day03>(),
+    ]
+}
+"#;
+        assert_eq!(Ok(expected.to_string()), register(LIB, 2));
+    }
+
+    #[test]
+    fn test_register_last() {
+        let lib = register(LIB, 12).unwrap();
+        assert!(lib.contains("pub mod day03;\n#[path = \"../day12/mod.rs\"]\npub mod day12;\n"));
+        assert!(lib.contains("Day::of::<day03::Day03>(),\n        Day::of::<day12::Day12>(),\n"));
+    }
+
+    #[test]
+    fn test_register_existing() {
+        assert!(register(LIB, 3).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let source = render(8);
+        assert!(source.contains("pub struct Day08;"));
+        assert!(source.contains("const DAY: u8 = 8;"));
+        assert!(source.contains("\"./day08/test1.txt\""));
+        assert!(!source.contains("00"));
+    }
+}
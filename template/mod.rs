@@ -0,0 +1,63 @@
+use crate::{ParseError, Solution};
+
+fn parse(s: &str) -> Result<Vec<String>, ParseError> {
+    Ok(s.lines().map(String::from).collect())
+}
+
+fn part1(lines: &[String]) -> usize {
+    lines.len()
+}
+
+fn part2(lines: &[String]) -> usize {
+    lines.len()
+}
+
+pub struct Day00;
+
+impl Solution for Day00 {
+    const DAY: u8 = 0;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answers::expected;
+    use crate::input::load;
+    use crate::Part;
+
+    #[test]
+    fn test_part1() {
+        let content = load("./day00/test1.txt").unwrap();
+        let result = part1(&parse(&content).unwrap());
+        assert_eq!(
+            expected(Day00::DAY, Part::One, "day00/test1.txt"),
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let content = load("./day00/test1.txt").unwrap();
+        let result = part2(&parse(&content).unwrap());
+        assert_eq!(
+            expected(Day00::DAY, Part::Two, "day00/test1.txt"),
+            result.to_string()
+        );
+    }
+}